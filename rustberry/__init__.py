@@ -1,3 +1,19 @@
-from ._rustberry import QueryCompiler
+from ._rustberry import (
+    GraphQLError,
+    OperationError,
+    ParseError,
+    QueryCompiler,
+    SchemaChange,
+    SchemaError,
+    diff_schemas,
+)
 
-__all__ = ('QueryCompiler',)
\ No newline at end of file
+__all__ = (
+    'GraphQLError',
+    'OperationError',
+    'ParseError',
+    'QueryCompiler',
+    'SchemaChange',
+    'SchemaError',
+    'diff_schemas',
+)
@@ -1,11 +1,149 @@
+from os import PathLike
+from typing import Any, Literal, Mapping
+
+from graphql.language.ast import FragmentDefinitionNode, OperationDefinitionNode
+
 class Document:
-    ...
+    source: str | None
+    def operation_names(self) -> list[str | None]: ...
+    def get_operation(self, name: str | None = None) -> str: ...
+    def operation_type(
+        self, name: str | None = None
+    ) -> Literal["query", "mutation", "subscription"]: ...
+    def fragment_names(self) -> list[str]: ...
+    def variable_definitions(self, name: str | None = None) -> dict[str, str]: ...
+
+class SourceLocation:
+    line: int
+    column: int
+
+class GraphQLError:
+    message: str
+    locations: list[SourceLocation]
+    path: list[str | int]
+    extensions: dict[str, Any] | None
+    formatted: dict[str, Any]
+
+class ParseError(Exception):
+    errors: list[GraphQLError]
+
+class SchemaError(Exception):
+    errors: list[GraphQLError]
+
+class OperationError(Exception):
+    errors: list[GraphQLError]
+
+class VisitedNode:
+    kind: str
+    name: str | None
+    alias: str | None
+    parent_type: str | None
+    type: str | None
+    operation: Literal["query", "mutation", "subscription"] | None
+    arguments: dict[str, str]
+    directives: list[str]
+    is_deprecated: bool
+    deprecation_reason: str | None
+
+class ValidationContext:
+    def report_error(
+        self, message: str, node: VisitedNode | None = None, code: str | None = None
+    ) -> None: ...
+
+class CacheInfo:
+    hits: int
+    misses: int
+    maxsize: int
+    currsize: int
+
+class SchemaChange:
+    criticality: Literal["BREAKING", "DANGEROUS", "SAFE"]
+    type: str
+    description: str
+
+_SchemaSources = (
+    str
+    | Mapping[str, str]
+    | list[str | PathLike[str]]
+    | tuple[str | PathLike[str], ...]
+)
+
+def diff_schemas(
+    old_schema: _SchemaSources, new_schema: _SchemaSources
+) -> list[SchemaChange]: ...
 
 class QueryCompiler:
-    def __init__(self, schema: str) -> None: ...
-    def parse(self, query: str) -> Document: ...
-    def validate(self, document: Document) -> bool: ...
-    def add_validate(self, contents: str) -> bool: ...
+    def __init__(
+        self,
+        schema: _SchemaSources,
+        assume_valid: bool = False,
+        max_depth: int | None = None,
+        max_cost: float | None = None,
+        disable_introspection: bool = False,
+        max_tokens: int | None = None,
+        max_recursion: int | None = None,
+        max_aliases: int | None = None,
+        max_root_fields: int | None = None,
+        max_directives_per_field: int | None = None,
+        cache_size: int = 0,
+        cache_ast: bool = False,
+    ) -> None: ...
+    @classmethod
+    def from_introspection(
+        cls, introspection: dict[str, Any] | str, **kwargs: Any
+    ) -> QueryCompiler: ...
+    def print_schema(self, sorted: bool = False, include_builtins: bool = False) -> str: ...
+    def diff(self, other: QueryCompiler) -> list[SchemaChange]: ...
+    def parse(self, query: str, source_name: str = "document.graphql") -> Document: ...
+    def validate(
+        self,
+        document: Document,
+        max_depth: int | None = None,
+        max_cost: float | None = None,
+        rules: list[Any] | None = None,
+    ) -> bool: ...
+    def validation_errors(
+        self,
+        document: Document,
+        max_depth: int | None = None,
+        max_cost: float | None = None,
+        rules: list[Any] | None = None,
+    ) -> list[GraphQLError]: ...
+    def add_validate(
+        self,
+        contents: str,
+        max_depth: int | None = None,
+        max_cost: float | None = None,
+    ) -> bool: ...
+    def validate_many(self, sources: list[str]) -> list[list[GraphQLError]]: ...
 
     def gql_core_ast(self, document: Document): ...
-    def gql_core_ast_mirror(self, document: Document): ...
\ No newline at end of file
+    def gql_core_ast_mirror(
+        self, document: Document, no_location: bool = False, lazy: bool = False
+    ): ...
+    def cache_info(self) -> CacheInfo: ...
+    def cache_clear(self) -> None: ...
+    def get_operation(
+        self,
+        document: Document,
+        operation_name: str | None = None,
+        no_location: bool = False,
+    ) -> tuple[OperationDefinitionNode, list[FragmentDefinitionNode]]: ...
+    def coerce_variables(
+        self,
+        document: Document,
+        operation_name: str | None,
+        variables: dict[str, Any],
+    ) -> dict[str, Any] | list[GraphQLError]: ...
+    def execute_introspection(
+        self,
+        document: Document,
+        operation_name: str | None = None,
+        variables: dict[str, Any] | None = None,
+    ) -> dict[str, Any]: ...
+    def estimate_cost(
+        self,
+        document: Document,
+        variables: dict[str, Any] | None = None,
+        operation_name: str | None = None,
+    ) -> dict[str, Any]: ...
@@ -1,9 +1,17 @@
+use std::ops::Deref;
+
 use apollo_compiler::{ExecutableDocument, Node};
-use apollo_compiler::executable::{Field, OperationType, Selection, SelectionSet};
+use apollo_compiler::executable::{
+    Argument, Field, Fragment, FragmentSpread, InlineFragment, Operation, OperationType,
+    Selection, SelectionSet, VariableDefinition,
+};
+use apollo_compiler::schema::{Directive, Type, Value};
 use pyo3::{PyAny, Python};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyString};
 
+use crate::ast::gql_core::mirror_converter::{definitions_in_source_order, ExecutableDefinition};
+
 struct CoreOperationType {
     Query: Py<PyAny>,
     Mutation: Py<PyAny>,
@@ -11,7 +19,7 @@ struct CoreOperationType {
 }
 
 impl CoreOperationType {
-    fn new(PyOperationType: &PyAny) -> CoreOperationType {
+    fn new(PyOperationType: &Bound<'_, PyAny>) -> CoreOperationType {
         let query = PyOperationType.getattr("QUERY").unwrap();
         let mutation = PyOperationType.getattr("MUTATION").unwrap();
         let subscription = PyOperationType.getattr("SUBSCRIPTION").unwrap();
@@ -41,142 +49,323 @@ pub struct CoreConversionContext {
     field_node: Py<PyAny>,
     document_node: Py<PyAny>,
     name_node: Py<PyAny>,
+    fragment_definition_node: Py<PyAny>,
+    fragment_spread_node: Py<PyAny>,
+    inline_fragment_node: Py<PyAny>,
+    argument_node: Py<PyAny>,
+    directive_node: Py<PyAny>,
+    variable_definition_node: Py<PyAny>,
+    variable_node: Py<PyAny>,
+    named_type_node: Py<PyAny>,
+    list_type_node: Py<PyAny>,
+    non_null_type_node: Py<PyAny>,
+    int_value_node: Py<PyAny>,
+    float_value_node: Py<PyAny>,
+    string_value_node: Py<PyAny>,
+    boolean_value_node: Py<PyAny>,
+    null_value_node: Py<PyAny>,
+    enum_value_node: Py<PyAny>,
+    list_value_node: Py<PyAny>,
+    object_value_node: Py<PyAny>,
+    object_field_node: Py<PyAny>,
 }
 
 impl CoreConversionContext {
     pub fn new(py: Python) -> Self {
-        let graphql_core_ast = PyModule::import(py, "graphql.language.ast").unwrap();
+        let graphql_core_ast = PyModule::import_bound(py, "graphql.language.ast").unwrap();
         let PyDocumentNode = graphql_core_ast.getattr("DocumentNode").unwrap();
         let PyOperationType = graphql_core_ast.getattr("OperationType").unwrap();
         let PyOperationDefinitionNode = graphql_core_ast.getattr("OperationDefinitionNode").unwrap();
         let PySelectionSetNode = graphql_core_ast.getattr("SelectionSetNode").unwrap();
         let PyFieldNode = graphql_core_ast.getattr("FieldNode").unwrap();
         let PyNameNode = graphql_core_ast.getattr("NameNode").unwrap();
+        let PyFragmentDefinitionNode = graphql_core_ast.getattr("FragmentDefinitionNode").unwrap();
+        let PyFragmentSpreadNode = graphql_core_ast.getattr("FragmentSpreadNode").unwrap();
+        let PyInlineFragmentNode = graphql_core_ast.getattr("InlineFragmentNode").unwrap();
+        let PyArgumentNode = graphql_core_ast.getattr("ArgumentNode").unwrap();
+        let PyDirectiveNode = graphql_core_ast.getattr("DirectiveNode").unwrap();
+        let PyVariableDefinitionNode = graphql_core_ast.getattr("VariableDefinitionNode").unwrap();
+        let PyVariableNode = graphql_core_ast.getattr("VariableNode").unwrap();
+        let PyNamedTypeNode = graphql_core_ast.getattr("NamedTypeNode").unwrap();
+        let PyListTypeNode = graphql_core_ast.getattr("ListTypeNode").unwrap();
+        let PyNonNullTypeNode = graphql_core_ast.getattr("NonNullTypeNode").unwrap();
+        let PyIntValueNode = graphql_core_ast.getattr("IntValueNode").unwrap();
+        let PyFloatValueNode = graphql_core_ast.getattr("FloatValueNode").unwrap();
+        let PyStringValueNode = graphql_core_ast.getattr("StringValueNode").unwrap();
+        let PyBooleanValueNode = graphql_core_ast.getattr("BooleanValueNode").unwrap();
+        let PyNullValueNode = graphql_core_ast.getattr("NullValueNode").unwrap();
+        let PyEnumValueNode = graphql_core_ast.getattr("EnumValueNode").unwrap();
+        let PyListValueNode = graphql_core_ast.getattr("ListValueNode").unwrap();
+        let PyObjectValueNode = graphql_core_ast.getattr("ObjectValueNode").unwrap();
+        let PyObjectFieldNode = graphql_core_ast.getattr("ObjectFieldNode").unwrap();
 
         Self {
-            operation_type: CoreOperationType::new(PyOperationType),
+            operation_type: CoreOperationType::new(&PyOperationType),
             operation_definition: PyOperationDefinitionNode.into(),
             selection_set_node: PySelectionSetNode.into(),
             field_node: PyFieldNode.into(),
             document_node: PyDocumentNode.into(),
             name_node: PyNameNode.into(),
+            fragment_definition_node: PyFragmentDefinitionNode.into(),
+            fragment_spread_node: PyFragmentSpreadNode.into(),
+            inline_fragment_node: PyInlineFragmentNode.into(),
+            argument_node: PyArgumentNode.into(),
+            directive_node: PyDirectiveNode.into(),
+            variable_definition_node: PyVariableDefinitionNode.into(),
+            variable_node: PyVariableNode.into(),
+            named_type_node: PyNamedTypeNode.into(),
+            list_type_node: PyListTypeNode.into(),
+            non_null_type_node: PyNonNullTypeNode.into(),
+            int_value_node: PyIntValueNode.into(),
+            float_value_node: PyFloatValueNode.into(),
+            string_value_node: PyStringValueNode.into(),
+            boolean_value_node: PyBooleanValueNode.into(),
+            null_value_node: PyNullValueNode.into(),
+            enum_value_node: PyEnumValueNode.into(),
+            list_value_node: PyListValueNode.into(),
+            object_value_node: PyObjectValueNode.into(),
+            object_field_node: PyObjectFieldNode.into(),
         }
     }
 
     fn get_name_nome(&self, py: Python, name: &str) -> PyResult<PyObject> {
-        let name_node_kwargs = PyDict::new(py);
+        let name_node_kwargs = PyDict::new_bound(py);
 
-        let name = PyString::new(py, name);
+        let name = PyString::new_bound(py, name);
         name_node_kwargs.set_item("value", name)?;
 
-        self.name_node.call(py, (), Some(name_node_kwargs))
+        self.name_node.call_bound(py, (), Some(&name_node_kwargs))
+    }
+
+    fn get_named_type_node(&self, py: Python, name: &str) -> PyResult<PyObject> {
+        let named_type_kwargs = PyDict::new_bound(py);
+        named_type_kwargs.set_item("name", self.get_name_nome(py, name)?)?;
+        self.named_type_node.call_bound(py, (), Some(&named_type_kwargs))
+    }
+
+    fn convert_arguments_to_core_arguments(&self, py: Python, arguments: &[Node<Argument>]) -> PyResult<PyObject> {
+        let core_arguments = PyList::empty_bound(py);
+        for argument in arguments {
+            let argument_kwargs = PyDict::new_bound(py);
+            argument_kwargs.set_item("name", self.get_name_nome(py, argument.name.as_str())?)?;
+            argument_kwargs.set_item("value", self.convert_value_to_core_value(py, &argument.value)?)?;
+            core_arguments.append(self.argument_node.call_bound(py, (), Some(&argument_kwargs))?)?;
+        }
+        Ok(core_arguments.to_object(py))
+    }
+
+    fn convert_directives_to_core_directives<'a>(
+        &self,
+        py: Python,
+        directives: impl IntoIterator<Item = &'a Node<Directive>>,
+    ) -> PyResult<PyObject> {
+        let core_directives = PyList::empty_bound(py);
+        for directive in directives {
+            let directive_kwargs = PyDict::new_bound(py);
+            directive_kwargs.set_item("name", self.get_name_nome(py, directive.name.as_str())?)?;
+            directive_kwargs.set_item("arguments", self.convert_arguments_to_core_arguments(py, &directive.arguments)?)?;
+            core_directives.append(self.directive_node.call_bound(py, (), Some(&directive_kwargs))?)?;
+        }
+        Ok(core_directives.to_object(py))
+    }
+
+    fn convert_type_to_core_type(&self, py: Python, ty: &Type) -> PyResult<PyObject> {
+        let type_kwargs = PyDict::new_bound(py);
+        match ty {
+            Type::Named(named_type) => self.get_named_type_node(py, named_type.as_str()),
+            Type::NonNullNamed(named_type) => {
+                type_kwargs.set_item("type", self.get_named_type_node(py, named_type.as_str())?)?;
+                self.non_null_type_node.call_bound(py, (), Some(&type_kwargs))
+            }
+            Type::List(list_type) => {
+                type_kwargs.set_item("type", self.convert_type_to_core_type(py, list_type)?)?;
+                self.list_type_node.call_bound(py, (), Some(&type_kwargs))
+            }
+            Type::NonNullList(list_type) => {
+                let list_type_kwargs = PyDict::new_bound(py);
+                list_type_kwargs.set_item("type", self.convert_type_to_core_type(py, list_type)?)?;
+                type_kwargs.set_item("type", self.list_type_node.call_bound(py, (), Some(&list_type_kwargs))?)?;
+                self.non_null_type_node.call_bound(py, (), Some(&type_kwargs))
+            }
+        }
+    }
+
+    fn convert_value_to_core_value(&self, py: Python, value: &Node<Value>) -> PyResult<PyObject> {
+        let value_kwargs = PyDict::new_bound(py);
+        match value.deref() {
+            Value::Null => self.null_value_node.call_bound(py, (), None),
+            Value::Enum(name) => {
+                value_kwargs.set_item("value", name.as_str())?;
+                self.enum_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::Variable(name) => {
+                value_kwargs.set_item("name", self.get_name_nome(py, name.as_str())?)?;
+                self.variable_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::String(string) => {
+                value_kwargs.set_item("value", string.to_string())?;
+                self.string_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::Float(float) => {
+                value_kwargs.set_item("value", float.to_string())?;
+                self.float_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::Int(int) => {
+                value_kwargs.set_item("value", int.to_string())?;
+                self.int_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::Boolean(boolean) => {
+                value_kwargs.set_item("value", *boolean)?;
+                self.boolean_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::List(values) => {
+                let core_values = PyList::empty_bound(py);
+                for value in values {
+                    core_values.append(self.convert_value_to_core_value(py, value)?)?;
+                }
+                value_kwargs.set_item("values", core_values)?;
+                self.list_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+            Value::Object(fields) => {
+                let core_fields = PyList::empty_bound(py);
+                for (name, value) in fields {
+                    let field_kwargs = PyDict::new_bound(py);
+                    field_kwargs.set_item("name", self.get_name_nome(py, name.as_str())?)?;
+                    field_kwargs.set_item("value", self.convert_value_to_core_value(py, value)?)?;
+                    core_fields.append(self.object_field_node.call_bound(py, (), Some(&field_kwargs))?)?;
+                }
+                value_kwargs.set_item("fields", core_fields)?;
+                self.object_value_node.call_bound(py, (), Some(&value_kwargs))
+            }
+        }
+    }
+
+    fn convert_variable_definition_to_core_variable_definition(
+        &self,
+        py: Python,
+        variable: &Node<VariableDefinition>,
+    ) -> PyResult<PyObject> {
+        let variable_kwargs = PyDict::new_bound(py);
+        variable_kwargs.set_item("name", self.get_name_nome(py, variable.name.as_str())?)?;
+
+        let variable_definition_kwargs = PyDict::new_bound(py);
+        variable_definition_kwargs.set_item("variable", self.variable_node.call_bound(py, (), Some(&variable_kwargs))?)?;
+        variable_definition_kwargs.set_item("type", self.convert_type_to_core_type(py, &variable.ty)?)?;
+        if let Some(default_value) = &variable.default_value {
+            variable_definition_kwargs.set_item("default_value", self.convert_value_to_core_value(py, default_value)?)?;
+        }
+        variable_definition_kwargs.set_item("directives", self.convert_directives_to_core_directives(py, variable.directives.iter())?)?;
+
+        self.variable_definition_node.call_bound(py, (), Some(&variable_definition_kwargs))
     }
 
     fn convert_field_to_core_field(&self, py: Python, field: &Node<Field>) -> PyResult<PyObject> {
-        //println!("Converting field to core field...");
-        let field_node_kwargs = PyDict::new(py);
-        if field.selection_set.selections.len() > 0 {
-            //println!("Field has selection set");
+        let field_node_kwargs = PyDict::new_bound(py);
+        if !field.selection_set.selections.is_empty() {
             let selection_set = self.convert_selection_set_to_core_selection_set(py, &field.selection_set)?;
             field_node_kwargs.set_item("selection_set", selection_set)?;
-            //println!("Selection set converted!");
         }
 
-        //println!("Alias");
         if let Some(alias) = &field.alias {
-            field_node_kwargs.set_item("alias", PyString::new(py, alias.as_str()))?;
+            field_node_kwargs.set_item("alias", self.get_name_nome(py, alias.as_str())?)?;
         }
 
-        //println!("Name");
         let name = self.get_name_nome(py, field.name.as_str())?;
         field_node_kwargs.set_item("name", name)?;
 
-        //println!("Initing lists");
-
-        // init an empty list of pyobjects
-        let arguments = PyList::empty(py).to_object(py);
-        let directives = PyList::empty(py).to_object(py);
-
+        field_node_kwargs.set_item("arguments", self.convert_arguments_to_core_arguments(py, &field.arguments)?)?;
+        field_node_kwargs.set_item("directives", self.convert_directives_to_core_directives(py, field.directives.iter())?)?;
 
-        field_node_kwargs.set_item("arguments", arguments)?;
-
-        field_node_kwargs.set_item("directives", directives)?;
+        self.field_node.call_bound(py, (), Some(&field_node_kwargs))
+    }
 
-        //println!("Calling field constructor");
+    fn convert_fragment_spread_to_core_fragment_spread(
+        &self,
+        py: Python,
+        fragment_spread: &Node<FragmentSpread>,
+    ) -> PyResult<PyObject> {
+        let fragment_spread_kwargs = PyDict::new_bound(py);
+        fragment_spread_kwargs.set_item("name", self.get_name_nome(py, fragment_spread.fragment_name.as_str())?)?;
+        fragment_spread_kwargs.set_item("directives", self.convert_directives_to_core_directives(py, fragment_spread.directives.iter())?)?;
+        self.fragment_spread_node.call_bound(py, (), Some(&fragment_spread_kwargs))
+    }
 
-        self.field_node.call(py, (), Some(field_node_kwargs))
+    fn convert_inline_fragment_to_core_inline_fragment(
+        &self,
+        py: Python,
+        inline_fragment: &Node<InlineFragment>,
+    ) -> PyResult<PyObject> {
+        let inline_fragment_kwargs = PyDict::new_bound(py);
+        if let Some(type_condition) = &inline_fragment.type_condition {
+            inline_fragment_kwargs.set_item("type_condition", self.get_named_type_node(py, type_condition.as_str())?)?;
+        }
+        inline_fragment_kwargs.set_item("directives", self.convert_directives_to_core_directives(py, inline_fragment.directives.iter())?)?;
+        inline_fragment_kwargs.set_item("selection_set", self.convert_selection_set_to_core_selection_set(py, &inline_fragment.selection_set)?)?;
+        self.inline_fragment_node.call_bound(py, (), Some(&inline_fragment_kwargs))
     }
 
     fn convert_selection_set_to_core_selection_set(&self, py: Python, selection_set: &SelectionSet) -> PyResult<PyObject> {
-        //println!("Converting selection set...");
-        let selection_set_kwargs = PyDict::new(py);
+        let selection_set_kwargs = PyDict::new_bound(py);
         // FIXME do we NEED to use PyTuple here?
-        let selections = PyList::empty(py);
+        let selections = PyList::empty_bound(py);
 
         for selection in &selection_set.selections {
             let core_selection = match selection {
-                Selection::Field(field) => Some(self.convert_field_to_core_field(py, field)),
-                Selection::FragmentSpread(fragment_spread) => None,//self.convert_fragment_spread_to_core_fragment_spread(py, fragment_spread),
-                Selection::InlineFragment(inline_fragment) => None,// self.convert_inline_fragment_to_core_inline_fragment(py, inline_fragment),
+                Selection::Field(field) => self.convert_field_to_core_field(py, field),
+                Selection::FragmentSpread(fragment_spread) => self.convert_fragment_spread_to_core_fragment_spread(py, fragment_spread),
+                Selection::InlineFragment(inline_fragment) => self.convert_inline_fragment_to_core_inline_fragment(py, inline_fragment),
             };
-            if let Some(core_selection) = core_selection {
-                //println!("Appending new Selection to the set...");
-                selections.append(core_selection?)?;
-            }
+            selections.append(core_selection?)?;
         }
-        //println!("Done converting selections!");
         selection_set_kwargs.set_item("selections", selections)?;
-        //println!("Appended selections to kwargs!");
-        self.selection_set_node.call(py, (), Some(selection_set_kwargs))
+        self.selection_set_node.call_bound(py, (), Some(&selection_set_kwargs))
     }
 
-    pub fn convert_core_to_core_ast(self: &Self, py: Python, document: &ExecutableDocument) -> PyResult<PyObject> {
-        let operations = document.all_operations();
-        let fragments = &document.fragments;
+    fn convert_operation_to_core_operation(&self, py: Python, operation: &Node<Operation>) -> PyResult<PyObject> {
+        let operation_kwargs = PyDict::new_bound(py);
 
-        let core_operations = PyList::empty(py);
-
-        for operation in operations {
-            let operation_kwargs = PyDict::new(py);
-
-            if let Some(operation_name) = &operation.name{
-                let operation_name = self.get_name_nome(py, operation_name)?;
-                // FIXME is this necessary?
-                //println!("Trying to set name!");
-                operation_kwargs.set_item("name", operation_name)?;
-                //println!("Name set!");
-            }
-
-
-            let operation_type = self.operation_type.get_operation_type(operation.operation_type);
+        if let Some(operation_name) = &operation.name {
+            operation_kwargs.set_item("name", self.get_name_nome(py, operation_name)?)?;
+        }
 
-            //println!("Operation type resolved!");
-            /*
-            directives: PyTuple["DirectiveNode", ...]
-            variable_definitions: Tuple["VariableDefinitionNode", ...]
-            selection_set: "SelectionSetNode" */
+        let operation_type = self.operation_type.get_operation_type(operation.operation_type);
+        operation_kwargs.set_item("operation", operation_type)?;
 
-            let directives = &operation.directives;
-            let variable_definitions = &operation.variables;
-            let selection_set = &operation.selection_set;
+        let variable_definitions = PyList::empty_bound(py);
+        for variable in &operation.variables {
+            variable_definitions.append(self.convert_variable_definition_to_core_variable_definition(py, variable)?)?;
+        }
+        operation_kwargs.set_item("variable_definitions", variable_definitions)?;
+        operation_kwargs.set_item("directives", self.convert_directives_to_core_directives(py, operation.directives.iter())?)?;
+        operation_kwargs.set_item("selection_set", self.convert_selection_set_to_core_selection_set(py, &operation.selection_set)?)?;
 
-            //println!("Selection sett, directives, variables done!");
+        self.operation_definition.call_bound(py, (), Some(&operation_kwargs))
+    }
 
-            operation_kwargs.set_item("operation", operation_type.into_ref(py))?;
+    fn convert_fragment_to_core_fragment(&self, py: Python, fragment: &Node<Fragment>) -> PyResult<PyObject> {
+        let fragment_kwargs = PyDict::new_bound(py);
 
-            //println!("Operation type set kwarg!");
-            operation_kwargs.set_item("selection_set", self.convert_selection_set_to_core_selection_set(py, selection_set)?)?;
-            //println!("Selection Set converted!");
+        fragment_kwargs.set_item("name", self.get_name_nome(py, fragment.name.as_str())?)?;
+        fragment_kwargs.set_item("type_condition", self.get_named_type_node(py, fragment.type_condition().as_str())?)?;
+        fragment_kwargs.set_item("directives", self.convert_directives_to_core_directives(py, fragment.directives.iter())?)?;
+        fragment_kwargs.set_item("variable_definitions", PyList::empty_bound(py))?;
+        fragment_kwargs.set_item("selection_set", self.convert_selection_set_to_core_selection_set(py, &fragment.selection_set)?)?;
 
-            //println!("Creating Operation def node...");
-            core_operations.append(self.operation_definition.call(py, (), Some(operation_kwargs))?)?;
+        self.fragment_definition_node.call_bound(py, (), Some(&fragment_kwargs))
+    }
 
-            //println!("Created Operation def node!");
+    pub fn convert_core_to_core_ast(self: &Self, py: Python, document: &ExecutableDocument) -> PyResult<PyObject> {
+        let definitions = PyList::empty_bound(py);
+        for definition in definitions_in_source_order(document) {
+            definitions.append(match definition {
+                ExecutableDefinition::Operation(operation) => self.convert_operation_to_core_operation(py, operation)?,
+                ExecutableDefinition::Fragment(fragment) => self.convert_fragment_to_core_fragment(py, fragment)?,
+            })?;
         }
-        let document_node_kwargs = PyDict::new(py);
-        document_node_kwargs.set_item("definitions", core_operations)?;
 
-        //println!("Creating document node!");
-        self.document_node.call(py, (), Some(document_node_kwargs))
+        let document_node_kwargs = PyDict::new_bound(py);
+        document_node_kwargs.set_item("definitions", definitions)?;
+
+        self.document_node.call_bound(py, (), Some(&document_node_kwargs))
     }
-}
\ No newline at end of file
+}
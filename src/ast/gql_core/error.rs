@@ -1,13 +1,90 @@
 use apollo_compiler::execution::{GraphQLError as ApolloGraphQLError, ResponseDataPathElement};
-use pyo3::{Py, PyAny, pyclass, pymethods, PyObject, PyResult, Python, ToPyObject};
-use pyo3::types::{PyInt, PyString};
+use apollo_compiler::validation::DiagnosticList;
+use apollo_compiler::{NodeLocation, SourceMap};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use pyo3::PyTypeInfo;
 
 use crate::ast::gql_core::imports::import_graphql_error;
 use crate::ast::gql_core::location::SourceLocation;
 
+create_exception!(
+    _rustberry,
+    ParseError,
+    PyException,
+    "Raised when a document cannot be parsed. `errors` holds one GraphQLError per diagnostic."
+);
+
+create_exception!(
+    _rustberry,
+    SchemaError,
+    PyException,
+    "Raised when a schema cannot be parsed or is invalid. `errors` holds one GraphQLError per diagnostic."
+);
+
+create_exception!(
+    _rustberry,
+    OperationError,
+    PyException,
+    "Raised when the operation to execute cannot be determined. `errors` holds the GraphQLError."
+);
+
 #[pyclass]
+#[derive(Clone)]
 pub struct GraphQLError {
     pub compiler_error: ApolloGraphQLError,
+    /// Machine readable identifier of the failed check, exposed as `extensions["code"]`.
+    pub code: Option<String>,
+}
+
+impl GraphQLError {
+    /// An error reported by rustberry itself rather than by apollo-compiler.
+    pub fn new(
+        message: impl Into<String>,
+        location: Option<NodeLocation>,
+        sources: &SourceMap,
+        code: Option<&str>,
+    ) -> Self {
+        GraphQLError {
+            compiler_error: ApolloGraphQLError::new(message, location, sources),
+            code: code.map(str::to_string),
+        }
+    }
+
+    /// Converts every diagnostic of an apollo-compiler `DiagnosticList` into a `GraphQLError`,
+    /// preferring the graphql-js compatible message where apollo-compiler provides one.
+    /// The apollo-compiler error name (e.g. `UndefinedField`) becomes the error code.
+    pub fn from_diagnostics(diagnostics: &DiagnosticList) -> Vec<GraphQLError> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| GraphQLError {
+                compiler_error: ApolloGraphQLError::new(
+                    diagnostic
+                        .error
+                        .unstable_compat_message()
+                        .unwrap_or(diagnostic.error.to_string()),
+                    diagnostic.error.location(),
+                    diagnostic.sources,
+                ),
+                code: diagnostic.error.unstable_error_name().map(str::to_string),
+            })
+            .collect()
+    }
+}
+
+/// Builds a Python exception of type `E` whose `errors` attribute lists the given errors.
+pub fn graphql_errors_to_py_err<E: PyTypeInfo>(
+    py: Python<'_>,
+    message: &str,
+    errors: Vec<GraphQLError>,
+) -> PyErr {
+    let err = PyErr::new::<E, _>(message.to_string());
+    if let Err(setattr_err) = err.value_bound(py).setattr("errors", errors.into_py(py)) {
+        return setattr_err;
+    }
+    err
 }
 
 #[pymethods]
@@ -50,8 +127,52 @@ impl GraphQLError {
     }
 
     #[getter]
-    pub fn extensions(&self) -> Option<&PyAny> {
-        // raise not supported error
-        panic!("Not supported yet")
+    pub fn extensions<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(code) = &self.code else {
+            return Ok(None);
+        };
+        let extensions = PyDict::new_bound(py);
+        extensions.set_item("code", code)?;
+        Ok(Some(extensions))
+    }
+
+    /// The error as a response-ready dict, matching graphql-core's `GraphQLError.formatted`.
+    #[getter]
+    pub fn formatted<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let formatted = PyDict::new_bound(py);
+        formatted.set_item("message", self.message())?;
+
+        let locations: Vec<Bound<PyDict>> = self
+            .compiler_error
+            .locations
+            .iter()
+            .map(|l| {
+                let location = PyDict::new_bound(py);
+                location.set_item("line", l.line)?;
+                location.set_item("column", l.column)?;
+                Ok(location)
+            })
+            .collect::<PyResult<_>>()?;
+        if !locations.is_empty() {
+            formatted.set_item("locations", locations)?;
+        }
+
+        let path = self.path(py);
+        if !path.is_empty() {
+            formatted.set_item("path", path)?;
+        }
+
+        if let Some(extensions) = self.extensions(py)? {
+            formatted.set_item("extensions", extensions)?;
+        }
+        Ok(formatted)
+    }
+
+    pub fn __str__(&self) -> &str {
+        self.message()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("GraphQLError({:?})", self.compiler_error.message)
     }
 }
@@ -21,3 +21,32 @@ pub fn import_source_location(py: Python<'_>) -> PyResult<PyObject> {
         .getattr("SourceLocation")?;
     Ok(location.into())
 }
+
+
+pub fn import_location(py: Python<'_>) -> PyResult<PyObject> {
+    let location = py
+        .import("graphql.language.ast")?
+        .getattr("Location")?;
+    Ok(location.into())
+}
+
+pub fn import_token(py: Python<'_>) -> PyResult<PyObject> {
+    let token = py
+        .import("graphql.language.ast")?
+        .getattr("Token")?;
+    Ok(token.into())
+}
+
+pub fn import_token_kind(py: Python<'_>) -> PyResult<PyObject> {
+    let token_kind = py
+        .import("graphql.language.token_kind")?
+        .getattr("TokenKind")?;
+    Ok(token_kind.into())
+}
+
+pub fn import_source(py: Python<'_>) -> PyResult<PyObject> {
+    let source = py
+        .import("graphql.language.source")?
+        .getattr("Source")?;
+    Ok(source.into())
+}
@@ -0,0 +1,504 @@
+use std::sync::Arc;
+
+use apollo_compiler::{ExecutableDocument, Node};
+use apollo_compiler::executable::{
+    Field, Fragment, InlineFragment, Operation, Selection, SelectionSet,
+};
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::PyList;
+
+use crate::ast::gql_core::location::{Location, SourceIndex};
+use crate::ast::gql_core::mirror_converter::{
+    definitions_in_source_order, selection_location, ExecutableDefinition,
+    MirrorConversionContext,
+};
+use crate::ast::gql_core::reduced_core_mirror::NamedTypeNode;
+
+/// State shared by every node of a lazily converted document.
+struct LazyDocument {
+    document: Arc<ExecutableDocument>,
+    sources: SourceIndex,
+    context: Arc<MirrorConversionContext>,
+}
+
+/// Returns the value of the cell, converting it on first access.
+fn cached(py: Python, cell: &GILOnceCell<PyObject>, convert: impl FnOnce() -> PyObject) -> PyObject {
+    cell.get_or_init(py, convert).clone_ref(py)
+}
+
+fn list<T: IntoPy<PyObject>>(py: Python, items: impl IntoIterator<Item = T>) -> PyObject {
+    PyList::new_bound(py, items.into_iter().map(|item| item.into_py(py))).into_py(py)
+}
+
+fn convert_selection(py: Python, shared: &Arc<LazyDocument>, selection: &Selection) -> PyObject {
+    match selection {
+        Selection::Field(field) => LazyFieldNode::new(shared, field).into_py(py),
+        // Fragment spreads are leaves, so they are converted right away
+        Selection::FragmentSpread(fragment_spread) => shared
+            .context
+            .convert_fragment_spread_to_core_fragment_spread(py, &shared.sources, fragment_spread)
+            .into_py(py),
+        Selection::InlineFragment(inline_fragment) => {
+            LazyInlineFragmentNode::new(shared, inline_fragment).into_py(py)
+        }
+    }
+}
+
+/// Mirror `DocumentNode` whose children are only converted once they are accessed.
+/// Behaves like `reduced_core_mirror::DocumentNode` otherwise.
+#[pyclass]
+pub struct LazyDocumentNode {
+    shared: Arc<LazyDocument>,
+    definitions: GILOnceCell<PyObject>,
+    #[pyo3(get)]
+    loc: Option<Location>,
+}
+
+impl LazyDocumentNode {
+    pub fn new(
+        context: Arc<MirrorConversionContext>,
+        document: Arc<ExecutableDocument>,
+        no_location: bool,
+    ) -> Self {
+        let sources = MirrorConversionContext::source_index(&document, no_location);
+        let file_id = definitions_in_source_order(&document)
+            .iter()
+            .find_map(|definition| definition.location().map(|location| location.file_id()));
+        let loc = sources.whole_file(file_id);
+
+        Self {
+            shared: Arc::new(LazyDocument {
+                document,
+                sources,
+                context,
+            }),
+            definitions: GILOnceCell::new(),
+            loc,
+        }
+    }
+}
+
+#[pymethods]
+impl LazyDocumentNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let document_node = py.import_bound("graphql.language.ast")?.getattr("DocumentNode")?;
+        Ok(document_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "document"
+    }
+
+    #[getter]
+    pub fn definitions(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.definitions, || {
+            let definitions = definitions_in_source_order(&shared.document);
+            list(py, definitions.into_iter().map(|definition| match definition {
+                ExecutableDefinition::Operation(operation) => {
+                    LazyOperationDefinitionNode::new(shared, operation).into_py(py)
+                }
+                ExecutableDefinition::Fragment(fragment) => {
+                    LazyFragmentDefinitionNode::new(shared, fragment).into_py(py)
+                }
+            }))
+        })
+    }
+}
+
+#[pyclass]
+pub struct LazyOperationDefinitionNode {
+    shared: Arc<LazyDocument>,
+    operation: Node<Operation>,
+    name: GILOnceCell<PyObject>,
+    directives: GILOnceCell<PyObject>,
+    variable_definitions: GILOnceCell<PyObject>,
+    selection_set: GILOnceCell<PyObject>,
+    #[pyo3(get)]
+    loc: Option<Location>,
+}
+
+impl LazyOperationDefinitionNode {
+    fn new(shared: &Arc<LazyDocument>, operation: &Node<Operation>) -> Self {
+        Self {
+            shared: shared.clone(),
+            operation: operation.clone(),
+            name: GILOnceCell::new(),
+            directives: GILOnceCell::new(),
+            variable_definitions: GILOnceCell::new(),
+            selection_set: GILOnceCell::new(),
+            loc: shared.sources.location(operation.location()),
+        }
+    }
+}
+
+#[pymethods]
+impl LazyOperationDefinitionNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("OperationDefinitionNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "operation_definition"
+    }
+
+    #[getter]
+    pub fn operation(&self) -> Py<PyAny> {
+        self.shared
+            .context
+            .operation_type
+            .get_operation_type(self.operation.operation_type)
+    }
+
+    #[getter]
+    pub fn name(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.name, || {
+            self.operation
+                .name
+                .as_ref()
+                .map(|name| shared.context.get_name_node(py, &shared.sources, name))
+                .into_py(py)
+        })
+    }
+
+    #[getter]
+    pub fn directives(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.directives, || {
+            list(py, self.operation.directives.iter().map(|directive| {
+                shared.context.convert_directive_to_core_directive(py, &shared.sources, directive)
+            }))
+        })
+    }
+
+    #[getter]
+    pub fn variable_definitions(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.variable_definitions, || {
+            list(py, self.operation.variables.iter().map(|variable| {
+                shared
+                    .context
+                    .convert_variable_definition_to_core_variable_definition(py, &shared.sources, variable)
+            }))
+        })
+    }
+
+    #[getter]
+    pub fn selection_set(&self, py: Python<'_>) -> PyObject {
+        cached(py, &self.selection_set, || {
+            LazySelectionSetNode::new(&self.shared, &self.operation.selection_set).into_py(py)
+        })
+    }
+}
+
+#[pyclass]
+pub struct LazyFragmentDefinitionNode {
+    shared: Arc<LazyDocument>,
+    fragment: Node<Fragment>,
+    name: GILOnceCell<PyObject>,
+    type_condition: GILOnceCell<PyObject>,
+    directives: GILOnceCell<PyObject>,
+    selection_set: GILOnceCell<PyObject>,
+    #[pyo3(get)]
+    loc: Option<Location>,
+}
+
+impl LazyFragmentDefinitionNode {
+    fn new(shared: &Arc<LazyDocument>, fragment: &Node<Fragment>) -> Self {
+        Self {
+            shared: shared.clone(),
+            fragment: fragment.clone(),
+            name: GILOnceCell::new(),
+            type_condition: GILOnceCell::new(),
+            directives: GILOnceCell::new(),
+            selection_set: GILOnceCell::new(),
+            loc: shared.sources.location(fragment.location()),
+        }
+    }
+}
+
+#[pymethods]
+impl LazyFragmentDefinitionNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("FragmentDefinitionNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "fragment_definition"
+    }
+
+    #[getter]
+    pub fn name(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.name, || {
+            shared
+                .context
+                .get_name_node(py, &shared.sources, &self.fragment.name)
+                .into_py(py)
+        })
+    }
+
+    #[getter]
+    pub fn type_condition(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.type_condition, || {
+            let type_condition = self.fragment.type_condition();
+            NamedTypeNode {
+                name: shared.context.get_name_node(py, &shared.sources, type_condition),
+                loc: shared.sources.location(type_condition.location()),
+            }
+            .into_py(py)
+        })
+    }
+
+    #[getter]
+    pub fn directives(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.directives, || {
+            list(py, self.fragment.directives.iter().map(|directive| {
+                shared.context.convert_directive_to_core_directive(py, &shared.sources, directive)
+            }))
+        })
+    }
+
+    /// apollo-compiler does not support the experimental fragment variables.
+    #[getter]
+    pub fn variable_definitions(&self, py: Python<'_>) -> PyObject {
+        PyList::empty_bound(py).into_py(py)
+    }
+
+    #[getter]
+    pub fn selection_set(&self, py: Python<'_>) -> PyObject {
+        cached(py, &self.selection_set, || {
+            LazySelectionSetNode::new(&self.shared, &self.fragment.selection_set).into_py(py)
+        })
+    }
+}
+
+#[pyclass]
+pub struct LazySelectionSetNode {
+    shared: Arc<LazyDocument>,
+    selection_set: SelectionSet,
+    selections: GILOnceCell<PyObject>,
+    #[pyo3(get)]
+    loc: Option<Location>,
+}
+
+impl LazySelectionSetNode {
+    fn new(shared: &Arc<LazyDocument>, selection_set: &SelectionSet) -> Self {
+        // apollo-compiler does not record the location of the braces, so approximate it
+        // with the span of the contained selections.
+        let loc = shared.sources.span(
+            selection_set.selections.first().and_then(selection_location),
+            selection_set.selections.last().and_then(selection_location),
+        );
+
+        Self {
+            shared: shared.clone(),
+            selection_set: selection_set.clone(),
+            selections: GILOnceCell::new(),
+            loc,
+        }
+    }
+}
+
+#[pymethods]
+impl LazySelectionSetNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("SelectionSetNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "selection_set"
+    }
+
+    #[getter]
+    pub fn selections(&self, py: Python<'_>) -> PyObject {
+        cached(py, &self.selections, || {
+            list(py, self.selection_set.selections.iter().map(|selection| {
+                convert_selection(py, &self.shared, selection)
+            }))
+        })
+    }
+}
+
+#[pyclass]
+pub struct LazyFieldNode {
+    shared: Arc<LazyDocument>,
+    field: Node<Field>,
+    alias: GILOnceCell<PyObject>,
+    name: GILOnceCell<PyObject>,
+    arguments: GILOnceCell<PyObject>,
+    directives: GILOnceCell<PyObject>,
+    selection_set: GILOnceCell<PyObject>,
+    #[pyo3(get)]
+    loc: Option<Location>,
+}
+
+impl LazyFieldNode {
+    fn new(shared: &Arc<LazyDocument>, field: &Node<Field>) -> Self {
+        Self {
+            shared: shared.clone(),
+            field: field.clone(),
+            alias: GILOnceCell::new(),
+            name: GILOnceCell::new(),
+            arguments: GILOnceCell::new(),
+            directives: GILOnceCell::new(),
+            selection_set: GILOnceCell::new(),
+            loc: shared.sources.location(field.location()),
+        }
+    }
+}
+
+#[pymethods]
+impl LazyFieldNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("FieldNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "field"
+    }
+
+    #[getter]
+    pub fn alias(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.alias, || {
+            self.field
+                .alias
+                .as_ref()
+                .map(|alias| shared.context.get_name_node(py, &shared.sources, alias))
+                .into_py(py)
+        })
+    }
+
+    #[getter]
+    pub fn name(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.name, || {
+            shared
+                .context
+                .get_name_node(py, &shared.sources, &self.field.name)
+                .into_py(py)
+        })
+    }
+
+    #[getter]
+    pub fn arguments(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.arguments, || {
+            list(py, self.field.arguments.iter().map(|argument| {
+                shared.context.convert_argument_to_core_argument(py, &shared.sources, argument)
+            }))
+        })
+    }
+
+    #[getter]
+    pub fn directives(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.directives, || {
+            list(py, self.field.directives.iter().map(|directive| {
+                shared.context.convert_directive_to_core_directive(py, &shared.sources, directive)
+            }))
+        })
+    }
+
+    /// `None` for leaf fields, like graphql-core.
+    #[getter]
+    pub fn selection_set(&self, py: Python<'_>) -> PyObject {
+        cached(py, &self.selection_set, || {
+            if self.field.selection_set.selections.is_empty() {
+                py.None()
+            } else {
+                LazySelectionSetNode::new(&self.shared, &self.field.selection_set).into_py(py)
+            }
+        })
+    }
+}
+
+#[pyclass]
+pub struct LazyInlineFragmentNode {
+    shared: Arc<LazyDocument>,
+    inline_fragment: Node<InlineFragment>,
+    type_condition: GILOnceCell<PyObject>,
+    directives: GILOnceCell<PyObject>,
+    selection_set: GILOnceCell<PyObject>,
+    #[pyo3(get)]
+    loc: Option<Location>,
+}
+
+impl LazyInlineFragmentNode {
+    fn new(shared: &Arc<LazyDocument>, inline_fragment: &Node<InlineFragment>) -> Self {
+        Self {
+            shared: shared.clone(),
+            inline_fragment: inline_fragment.clone(),
+            type_condition: GILOnceCell::new(),
+            directives: GILOnceCell::new(),
+            selection_set: GILOnceCell::new(),
+            loc: shared.sources.location(inline_fragment.location()),
+        }
+    }
+}
+
+#[pymethods]
+impl LazyInlineFragmentNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("InlineFragmentNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "inline_fragment"
+    }
+
+    #[getter]
+    pub fn type_condition(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.type_condition, || {
+            self.inline_fragment
+                .type_condition
+                .as_ref()
+                .map(|type_condition| NamedTypeNode {
+                    name: shared.context.get_name_node(py, &shared.sources, type_condition),
+                    loc: shared.sources.location(type_condition.location()),
+                })
+                .into_py(py)
+        })
+    }
+
+    #[getter]
+    pub fn directives(&self, py: Python<'_>) -> PyObject {
+        let shared = &self.shared;
+        cached(py, &self.directives, || {
+            list(py, self.inline_fragment.directives.iter().map(|directive| {
+                shared.context.convert_directive_to_core_directive(py, &shared.sources, directive)
+            }))
+        })
+    }
+
+    #[getter]
+    pub fn selection_set(&self, py: Python<'_>) -> PyObject {
+        cached(py, &self.selection_set, || {
+            LazySelectionSetNode::new(&self.shared, &self.inline_fragment.selection_set)
+                .into_py(py)
+        })
+    }
+}
@@ -1,7 +1,15 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apollo_compiler::{FileId, NodeLocation, SourceFile, SourceMap};
+use apollo_parser::{Lexer, Token as LexerToken, TokenKind};
 use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
 use pyo3::types::{IntoPyDict, PyDict};
 
-use crate::ast::gql_core::imports::import_source_location;
+use crate::ast::gql_core::imports::{
+    import_location, import_source, import_source_location, import_token, import_token_kind,
+};
 
 /// Wrapper for SourceLocation class
 #[pyclass]
@@ -35,3 +43,246 @@ impl SourceLocation {
     //     Ok(!self.eq(other, py)?)
     // }
 }
+
+/// Source text of a parsed document, shared by all `Location`s pointing into it.
+pub struct LocationSource {
+    file: Arc<SourceFile>,
+    py_source: GILOnceCell<PyObject>,
+}
+
+impl LocationSource {
+    fn body(&self) -> &str {
+        self.file.source_text()
+    }
+
+    /// 1-based line and column of the byte offset, as graphql-core reports them.
+    fn line_column(&self, offset: usize) -> (usize, usize) {
+        let body = self.body();
+        let before = &body[..offset.min(body.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
+}
+
+/// Resolves apollo-compiler node locations to mirror `Location`s.
+/// An empty index (see `SourceIndex::disabled`) yields no locations at all,
+/// which is the equivalent of graphql-core's `parse(..., no_location=True)`.
+#[derive(Default)]
+pub struct SourceIndex {
+    sources: HashMap<FileId, Arc<LocationSource>>,
+}
+
+impl SourceIndex {
+    pub fn new(sources: &SourceMap) -> Self {
+        let sources = sources
+            .iter()
+            .map(|(file_id, source_file)| {
+                let source = LocationSource {
+                    file: source_file.clone(),
+                    py_source: GILOnceCell::new(),
+                };
+                (*file_id, Arc::new(source))
+            })
+            .collect();
+        Self { sources }
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn location(&self, node_location: Option<NodeLocation>) -> Option<Location> {
+        let node_location = node_location?;
+        let source = self.sources.get(&node_location.file_id())?;
+        Some(Location {
+            start: node_location.offset(),
+            end: node_location.end_offset(),
+            source: source.clone(),
+        })
+    }
+
+    /// A location spanning from the start of `first` to the end of `last`.
+    pub fn span(
+        &self,
+        first: Option<NodeLocation>,
+        last: Option<NodeLocation>,
+    ) -> Option<Location> {
+        let (first, last) = (self.location(first)?, self.location(last)?);
+        if !Arc::ptr_eq(&first.source, &last.source) {
+            return None;
+        }
+        Some(Location {
+            start: first.start,
+            end: last.end,
+            source: first.source,
+        })
+    }
+
+    /// A location covering the whole source of the given file, used for `DocumentNode.loc`.
+    pub fn whole_file(&self, file_id: Option<FileId>) -> Option<Location> {
+        let source = self.sources.get(&file_id?)?;
+        Some(Location {
+            start: 0,
+            end: source.body().len(),
+            source: source.clone(),
+        })
+    }
+}
+
+/// Wrapper for the Location class. Tokens and the Source object are only created when accessed.
+#[pyclass]
+#[derive(Clone)]
+pub struct Location {
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+    source: Arc<LocationSource>,
+}
+
+impl Location {
+    /// Lexes the located text lazily, yielding its significant tokens up to the end of input.
+    fn tokens(&self) -> impl Iterator<Item = LexerToken<'_>> {
+        Lexer::new(&self.source.body()[self.start..self.end])
+            .filter_map(Result::ok)
+            .filter(|token| {
+                !matches!(
+                    token.kind(),
+                    TokenKind::Whitespace | TokenKind::Comma | TokenKind::Comment
+                )
+            })
+            .take_while(|token| token.kind() != TokenKind::Eof)
+    }
+
+    /// Builds the mirror token, resolving its line and column only now that it is needed.
+    fn token(&self, token: LexerToken<'_>) -> Token {
+        let start = self.start + token.index();
+        let (line, column) = self.source.line_column(start);
+        Token {
+            kind: token.kind(),
+            start,
+            end: start + token.data().len(),
+            line,
+            column,
+            data: token.data().to_string(),
+        }
+    }
+}
+
+#[pymethods]
+impl Location {
+    #[getter(__class__)]
+    fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        import_location(py)
+    }
+
+    #[getter]
+    fn start_token(&self) -> Option<Token> {
+        self.tokens().next().map(|token| self.token(token))
+    }
+
+    #[getter]
+    fn end_token(&self) -> Option<Token> {
+        self.tokens().last().map(|token| self.token(token))
+    }
+
+    #[getter]
+    fn source(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.source
+            .py_source
+            .get_or_try_init(py, || {
+                let name = self.source.file.path().display().to_string();
+                import_source(py)?.call1(py, (self.source.body(), name))
+            })
+            .map(|source| source.clone_ref(py))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Location {}:{}>", self.start, self.end)
+    }
+}
+
+/// Wrapper for the Token class. `prev` and `next` are not linked.
+#[pyclass]
+#[derive(Clone)]
+pub struct Token {
+    kind: TokenKind,
+    #[pyo3(get)]
+    start: usize,
+    #[pyo3(get)]
+    end: usize,
+    #[pyo3(get)]
+    line: usize,
+    #[pyo3(get)]
+    column: usize,
+    data: String,
+}
+
+#[pymethods]
+impl Token {
+    #[getter(__class__)]
+    fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        import_token(py)
+    }
+
+    #[getter]
+    fn kind(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let kind = match self.kind {
+            TokenKind::Bang => "!",
+            TokenKind::Dollar => "$",
+            TokenKind::Amp => "&",
+            TokenKind::Spread => "...",
+            TokenKind::Colon => ":",
+            TokenKind::Eq => "=",
+            TokenKind::At => "@",
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LBracket => "[",
+            TokenKind::RBracket => "]",
+            TokenKind::LCurly => "{",
+            TokenKind::RCurly => "}",
+            TokenKind::Pipe => "|",
+            TokenKind::Name => "Name",
+            TokenKind::Int => "Int",
+            TokenKind::Float => "Float",
+            TokenKind::StringValue if self.data.starts_with("\"\"\"") => "BlockString",
+            TokenKind::StringValue => "String",
+            TokenKind::Comment => "Comment",
+            TokenKind::Whitespace | TokenKind::Comma | TokenKind::Eof => {
+                unreachable!("insignificant tokens are skipped by Location::tokens")
+            }
+        };
+        import_token_kind(py)?.call1(py, (kind,))
+    }
+
+    #[getter]
+    fn value(&self) -> Option<&str> {
+        match self.kind {
+            TokenKind::Name | TokenKind::Int | TokenKind::Float | TokenKind::Comment => {
+                Some(self.data.as_str())
+            }
+            TokenKind::StringValue => {
+                let quotes = if self.data.starts_with("\"\"\"") { 3 } else { 1 };
+                self.data
+                    .get(quotes..self.data.len().saturating_sub(quotes))
+                    .or(Some(""))
+            }
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn prev(&self) -> Option<Token> {
+        None
+    }
+
+    #[getter]
+    fn next(&self) -> Option<Token> {
+        None
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Token {:?} {}/{}>", self.data, self.line, self.column)
+    }
+}
@@ -1,15 +1,21 @@
+use std::collections::HashMap;
 use std::ops::Deref;
 
-use apollo_compiler::{ExecutableDocument, Node};
-use apollo_compiler::executable::{Argument, Field, OperationType, Selection, SelectionSet};
-use apollo_compiler::schema::{Directive, Type, Value};
+use apollo_compiler::{ExecutableDocument, Name, Node, NodeLocation, Schema};
+use apollo_compiler::executable::{
+    Argument, Field, Fragment, FragmentSpread, InlineFragment, Operation, OperationType,
+    Selection, SelectionSet, VariableDefinition,
+};
+use apollo_compiler::schema::{Directive, ExtendedType, Type, Value};
 use pyo3::{PyAny, Python};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::PyString;
 
+use crate::ast::gql_core::location::SourceIndex;
+use crate::ast::operation::collect_fragments;
 use crate::ast::gql_core::reduced_core_mirror::*;
 
-struct CoreOperationType {
+pub struct CoreOperationType {
     Query: Py<PyAny>,
     Mutation: Py<PyAny>,
     Subscription: Py<PyAny>,
@@ -28,7 +34,7 @@ impl CoreOperationType {
         }
     }
 
-    fn get_operation_type(&self, operation_type: OperationType) -> Py<PyAny> {
+    pub fn get_operation_type(&self, operation_type: OperationType) -> Py<PyAny> {
         match operation_type {
             OperationType::Query => self.Query.to_owned(),
             OperationType::Mutation => self.Mutation.to_owned(),
@@ -37,18 +43,97 @@ impl CoreOperationType {
     }
 }
 
+pub fn selection_location(selection: &Selection) -> Option<NodeLocation> {
+    match selection {
+        Selection::Field(field) => field.location(),
+        Selection::FragmentSpread(fragment_spread) => fragment_spread.location(),
+        Selection::InlineFragment(inline_fragment) => inline_fragment.location(),
+    }
+}
+
+/// An operation or fragment definition of an executable document.
+pub enum ExecutableDefinition<'a> {
+    Operation(&'a Node<Operation>),
+    Fragment(&'a Node<Fragment>),
+}
+
+impl ExecutableDefinition<'_> {
+    pub fn location(&self) -> Option<NodeLocation> {
+        match self {
+            ExecutableDefinition::Operation(operation) => operation.location(),
+            ExecutableDefinition::Fragment(fragment) => fragment.location(),
+        }
+    }
+}
+
+/// All definitions of the document in source order, as graphql-core expects them.
+/// apollo-compiler keeps operations and fragments apart, so they are interleaved by their
+/// offset. Definitions without a location are kept at the end.
+pub fn definitions_in_source_order(document: &ExecutableDocument) -> Vec<ExecutableDefinition<'_>> {
+    let mut definitions: Vec<ExecutableDefinition> = document
+        .all_operations()
+        .map(ExecutableDefinition::Operation)
+        .chain(document.fragments.values().map(ExecutableDefinition::Fragment))
+        .collect();
+    definitions.sort_by_key(|definition| {
+        definition.location().map_or(usize::MAX, |location| location.offset())
+    });
+    definitions
+}
+
 pub struct MirrorConversionContext {
     //graphql_core_ast: Py<PyModule>,
-    operation_type: CoreOperationType,
+    pub operation_type: CoreOperationType,
     operation_definition: Py<PyAny>,
     selection_set_node: Py<PyAny>,
     field_node: Py<PyAny>,
     document_node: Py<PyAny>,
     name_node: Py<PyAny>,
+    /// Interned strings of the names defined by the schema, shared by the name nodes
+    /// of every converted document.
+    names: HashMap<String, Py<PyString>>,
+}
+
+/// Interns the names of types, fields, arguments, enum values and directives of the schema.
+fn intern_schema_names(py: Python, schema: &Schema) -> HashMap<String, Py<PyString>> {
+    let mut names = HashMap::new();
+    let mut intern = |name: &Name| {
+        names
+            .entry(name.to_string())
+            .or_insert_with(|| PyString::intern_bound(py, name.as_str()).unbind());
+    };
+
+    for (type_name, ty) in &schema.types {
+        intern(type_name);
+        match ty {
+            ExtendedType::Object(object) => {
+                for (field_name, field) in &object.fields {
+                    intern(field_name);
+                    field.arguments.iter().for_each(|argument| intern(&argument.name));
+                }
+            }
+            ExtendedType::Interface(interface) => {
+                for (field_name, field) in &interface.fields {
+                    intern(field_name);
+                    field.arguments.iter().for_each(|argument| intern(&argument.name));
+                }
+            }
+            ExtendedType::InputObject(input_object) => {
+                input_object.fields.keys().for_each(&mut intern);
+            }
+            ExtendedType::Enum(enum_type) => {
+                enum_type.values.keys().for_each(&mut intern);
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+        }
+    }
+    schema.directive_definitions.keys().for_each(&mut intern);
+
+    names
 }
 
 impl MirrorConversionContext {
-    pub fn new(py: Python) -> Self {
+    pub fn new(py: Python, schema: &Schema) -> Self {
         let graphql_core_ast = PyModule::import(py, "graphql.language.ast").unwrap();
         let PyDocumentNode = graphql_core_ast.getattr("DocumentNode").unwrap();
         let PyOperationType = graphql_core_ast.getattr("OperationType").unwrap();
@@ -65,38 +150,52 @@ impl MirrorConversionContext {
             field_node: PyFieldNode.into(),
             document_node: PyDocumentNode.into(),
             name_node: PyNameNode.into(),
+            names: intern_schema_names(py, schema),
         }
     }
 
-    fn get_name_node(&self, py: Python, name: &str) -> NameNode {
+    /// Names defined by the schema share their interned string, others such as aliases
+    /// and variable names get a new one.
+    pub fn get_name_node(&self, py: Python, sources: &SourceIndex, name: &Name) -> NameNode {
+        let value = match self.names.get(name.as_str()) {
+            Some(value) => value.clone_ref(py),
+            None => PyString::new_bound(py, name.as_str()).unbind(),
+        };
         NameNode {
-            value: name.to_string(),
+            value,
+            loc: sources.location(name.location()),
         }
     }
 
-    fn convert_argument_to_core_argument(&self, py: Python, argument: &Node<Argument>) -> ArgumentNode {
-        let name = self.get_name_node(py, argument.name.as_str());
-        let value = self.convert_value_to_core_value(py, &argument.value);
+    pub fn convert_argument_to_core_argument(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        argument: &Node<Argument>,
+    ) -> ArgumentNode {
+        let name = self.get_name_node(py, sources, &argument.name);
+        let value = self.convert_value_to_core_value(py, sources, &argument.value);
         ArgumentNode {
             name,
             value,
+            loc: sources.location(argument.location()),
         }
     }
 
-    fn convert_field_to_core_field(&self, py: Python, field: &Node<Field>) -> FieldNode {
+    fn convert_field_to_core_field(&self, py: Python, sources: &SourceIndex, field: &Node<Field>) -> FieldNode {
         let selection_set = field.selection_set.selections.first()
-            .map(|_| self.convert_selection_set_to_core_selection_set(py, &field.selection_set));
+            .map(|_| self.convert_selection_set_to_core_selection_set(py, sources, &field.selection_set));
 
-        let alias = field.alias.as_ref().map(|field_alias| self.get_name_node(py, field_alias.as_str()));
+        let alias = field.alias.as_ref().map(|field_alias| self.get_name_node(py, sources, field_alias));
 
-        let name = self.get_name_node(py, field.name.as_str());
+        let name = self.get_name_node(py, sources, &field.name);
 
         let arguments = field.arguments.iter().map(|argument| {
-            self.convert_argument_to_core_argument(py, argument)
+            self.convert_argument_to_core_argument(py, sources, argument)
         }).collect();
 
         let directives = field.directives.iter().map(|directive| {
-            self.convert_directive_to_core_directive(py, directive)
+            self.convert_directive_to_core_directive(py, sources, directive)
         }).collect();
 
         FieldNode {
@@ -105,82 +204,159 @@ impl MirrorConversionContext {
             arguments,
             directives,
             selection_set,
+            loc: sources.location(field.location()),
+        }
+    }
+
+    pub fn convert_fragment_spread_to_core_fragment_spread(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        fragment_spread: &Node<FragmentSpread>,
+    ) -> FragmentSpreadNode {
+        let name = self.get_name_node(py, sources, &fragment_spread.fragment_name);
+
+        let directives = fragment_spread.directives.iter().map(|directive| {
+            self.convert_directive_to_core_directive(py, sources, directive)
+        }).collect();
+
+        FragmentSpreadNode {
+            name,
+            directives,
+            loc: sources.location(fragment_spread.location()),
+        }
+    }
+
+    fn convert_inline_fragment_to_core_inline_fragment(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        inline_fragment: &Node<InlineFragment>,
+    ) -> InlineFragmentNode {
+        let type_condition = inline_fragment.type_condition.as_ref().map(|type_condition| {
+            NamedTypeNode {
+                name: self.get_name_node(py, sources, type_condition),
+                loc: sources.location(type_condition.location()),
+            }
+        });
+
+        let directives = inline_fragment.directives.iter().map(|directive| {
+            self.convert_directive_to_core_directive(py, sources, directive)
+        }).collect();
+
+        let selection_set =
+            self.convert_selection_set_to_core_selection_set(py, sources, &inline_fragment.selection_set);
+
+        InlineFragmentNode {
+            type_condition,
+            directives,
+            selection_set,
+            loc: sources.location(inline_fragment.location()),
         }
     }
 
     fn convert_selection_set_to_core_selection_set(
         &self,
         py: Python,
+        sources: &SourceIndex,
         selection_set: &SelectionSet,
     ) -> SelectionSetNode {
-        //println!("Converting selection set...");
-        let selection_set_kwargs = PyDict::new(py);
-        let selections: Vec<FieldNode> = selection_set
+        let selections: Vec<PyObject> = selection_set
             .selections
             .iter()
-            .filter_map(|selection| {
-                let value = match selection {
-                    Selection::Field(field) => Some(self.convert_field_to_core_field(py, field)),
-                    Selection::FragmentSpread(fragment_spread) => None, //self.convert_fragment_spread_to_core_fragment_spread(py, fragment_spread),
-                    Selection::InlineFragment(inline_fragment) => None, // self.convert_inline_fragment_to_core_inline_fragment(py, inline_fragment),
-                };
-                value
+            .map(|selection| match selection {
+                Selection::Field(field) => self.convert_field_to_core_field(py, sources, field).into_py(py),
+                Selection::FragmentSpread(fragment_spread) => self
+                    .convert_fragment_spread_to_core_fragment_spread(py, sources, fragment_spread)
+                    .into_py(py),
+                Selection::InlineFragment(inline_fragment) => self
+                    .convert_inline_fragment_to_core_inline_fragment(py, sources, inline_fragment)
+                    .into_py(py),
             })
             .collect();
 
+        // apollo-compiler does not record the location of the braces, so approximate it
+        // with the span of the contained selections.
+        let loc = sources.span(
+            selection_set.selections.first().and_then(selection_location),
+            selection_set.selections.last().and_then(selection_location),
+        );
+
         SelectionSetNode {
             selections,
+            loc,
         }
     }
-    fn convert_type_to_core_type(&self, py: Python, ty: &Type) -> PyObject {
+
+    /// Converts a type reference. Only the outermost type carries a location,
+    /// as apollo-compiler does not track locations of nested types.
+    fn convert_type_to_core_type(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        ty: &Type,
+        location: Option<NodeLocation>,
+    ) -> PyObject {
+        let loc = sources.location(location);
         match ty {
             Type::Named(named_type) => {
-                let name = self.get_name_node(py, named_type.as_str());
+                let name = self.get_name_node(py, sources, named_type);
                 let core_named_type = NamedTypeNode {
                     name,
+                    loc,
                 };
                 core_named_type.into_py(py)
             }
             Type::NonNullNamed(named_type) => {
                 let core_named_type = NonNullTypeNode {
                     r#type: NamedTypeNode {
-                        name: self.get_name_node(py, named_type.as_str()),
+                        name: self.get_name_node(py, sources, named_type),
+                        loc: sources.location(named_type.location()),
                     }.into_py(py),
+                    loc,
                 };
                 core_named_type.into_py(py)
             }
             Type::List(list_type) => {
                 let core_list_type = ListTypeNode {
-                    r#type: self.convert_type_to_core_type(py, list_type),
+                    r#type: self.convert_type_to_core_type(py, sources, list_type, None),
+                    loc,
                 };
                 core_list_type.into_py(py)
             }
             Type::NonNullList(list_type) => {
                 let core_list_type = NonNullTypeNode {
                     r#type: ListTypeNode {
-                        r#type: self.convert_type_to_core_type(py, list_type),
+                        r#type: self.convert_type_to_core_type(py, sources, list_type, None),
+                        loc: None,
                     }.into_py(py),
+                    loc,
                 };
                 core_list_type.into_py(py)
             }
         }
     }
 
-    fn convert_value_to_core_value(&self, py: Python, value: &Node<Value>) -> PyObject {
+    fn convert_value_to_core_value(&self, py: Python, sources: &SourceIndex, value: &Node<Value>) -> PyObject {
+        let loc = sources.location(value.location());
         match value.deref() {
             Value::Null => {
-                let core_value = NullValueNode {};
+                let core_value = NullValueNode {
+                    loc,
+                };
                 core_value.into_py(py)
             }
             Value::Enum(name) => {
                 let core_value = EnumValueNode {
                     value: name.to_string(),
+                    loc,
                 };
                 core_value.into_py(py)
             }
             Value::Variable(name) => {
                 let core_value = VariableNode {
-                    name: self.get_name_node(py, name.as_str()),
+                    name: self.get_name_node(py, sources, name),
+                    loc,
                 };
                 core_value.into_py(py)
             }
@@ -188,128 +364,223 @@ impl MirrorConversionContext {
                 let core_value = StringValueNode {
                     value: string.to_string(),
                     block: None, // FIXME do we have an equivalent in apollo rs
+                    loc,
                 };
                 core_value.into_py(py)
             }
             Value::Float(float) => {
                 let core_value = FloatValueNode {
                     value: float.to_string(),
+                    loc,
                 };
                 core_value.into_py(py)
             }
             Value::Int(int) => {
                 let core_value = IntValueNode {
                     value: int.to_string(),
+                    loc,
                 };
                 core_value.into_py(py)
             }
             Value::Boolean(boolean) => {
                 let core_value = BooleanValueNode {
                     value: *boolean,
+                    loc,
                 };
                 core_value.into_py(py)
             }
             Value::List(values) => {
                 let core_values: Vec<PyObject> = values.iter().map(|value| {
-                    self.convert_value_to_core_value(py, value)
+                    self.convert_value_to_core_value(py, sources, value)
                 }).collect();
                 let core_value = ListValueNode {
                     values: core_values,
+                    loc,
                 };
                 core_value.into_py(py)
             }
             Value::Object(fields) => {
                 let core_fields: Vec<ObjectFieldNode> = fields.iter().map(|(name, value)| {
                     ObjectFieldNode {
-                        name: self.get_name_node(py, name.as_str()),
-                        value: self.convert_value_to_core_value(py, value),
+                        name: self.get_name_node(py, sources, name),
+                        value: self.convert_value_to_core_value(py, sources, value),
+                        loc: sources.span(name.location(), value.location()),
                     }
                 }).collect();
                 let core_value = ObjectValueNode {
                     fields: core_fields,
+                    loc,
                 };
                 core_value.into_py(py)
             }
         }
     }
 
-    fn convert_directive_to_core_directive(&self, py: Python, directive: &Node<Directive>) -> DirectiveNode {
-        let name = self.get_name_node(py, directive.name.as_str());
+    pub fn convert_directive_to_core_directive(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        directive: &Node<Directive>,
+    ) -> DirectiveNode {
+        let name = self.get_name_node(py, sources, &directive.name);
         let arguments = directive.arguments.iter().map(|argument| {
-            self.convert_argument_to_core_argument(py, argument)
+            self.convert_argument_to_core_argument(py, sources, argument)
         }).collect();
         DirectiveNode {
             name,
             arguments,
+            loc: sources.location(directive.location()),
         }
     }
 
-    pub fn convert_core_to_core_ast(
-        self: &Self,
+    pub fn convert_variable_definition_to_core_variable_definition(
+        &self,
         py: Python,
-        document: &ExecutableDocument,
-    ) -> DocumentNode {
-        let operations = document.all_operations();
-        let fragments = &document.fragments;
-
-        let core_operations: Vec<OperationDefinitionNode> = operations
-            .map(|operation| {
-                let operation_kwargs = PyDict::new_bound(py);
+        sources: &SourceIndex,
+        variable: &Node<VariableDefinition>,
+    ) -> VariableDefinitionNode {
+        let name = self.get_name_node(py, sources, &variable.name);
+        let variable_type =
+            self.convert_type_to_core_type(py, sources, variable.ty.deref(), variable.ty.location());
+
+        let default_value = variable.default_value.as_ref().map(|value| {
+            self.convert_value_to_core_value(py, sources, value)
+        });
+
+        let directives = variable.directives.iter().map(|directive| {
+            self.convert_directive_to_core_directive(py, sources, directive)
+        }).collect();
 
-                let operation_name = operation.name.as_ref().map(|name| self.get_name_node(py, name.as_str()));
+        VariableDefinitionNode {
+            variable: VariableNode {
+                loc: name.loc.clone(),
+                name,
+            },
+            default_value,
+            directives,
+            r#type: variable_type,
+            loc: sources.location(variable.location()),
+        }
+    }
 
-                let operation_type = self
-                    .operation_type
-                    .get_operation_type(operation.operation_type);
+    fn convert_operation_to_core_operation(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        operation: &Node<Operation>,
+    ) -> OperationDefinitionNode {
+        let operation_name = operation.name.as_ref().map(|name| self.get_name_node(py, sources, name));
 
-                let directives: Vec<DirectiveNode> = operation.directives.iter().map(|directive| {
-                    self.convert_directive_to_core_directive(py, directive)
-                }).collect();
+        let operation_type = self
+            .operation_type
+            .get_operation_type(operation.operation_type);
 
+        let variable_definitions = operation.variables.iter().map(|variable| {
+            self.convert_variable_definition_to_core_variable_definition(py, sources, variable)
+        }).collect();
 
-                let variable_definitions: Vec<VariableDefinitionNode> = operation.variables.iter().map(|variable| {
-                    let name = self.get_name_node(py, variable.name.as_str());
-                    let variable_type = self.convert_type_to_core_type(py, variable.ty.deref());
+        let directives = operation.directives.iter().map(|directive| {
+            self.convert_directive_to_core_directive(py, sources, directive)
+        }).collect();
 
-                    let default_value = variable.default_value.as_ref().map(|value| {
-                        self.convert_value_to_core_value(py, value)
-                    });
+        let selection_set =
+            self.convert_selection_set_to_core_selection_set(py, sources, &operation.selection_set);
 
-                    let variable_directives = variable.directives.iter().map(|directive| {
-                        self.convert_directive_to_core_directive(py, directive)
-                    }).collect();
+        OperationDefinitionNode {
+            operation: operation_type,
+            name: operation_name,
+            variable_definitions,
+            directives,
+            selection_set,
+            loc: sources.location(operation.location()),
+        }
+    }
 
-                    VariableDefinitionNode {
-                        variable: VariableNode {
-                            name,
-                        },
-                        default_value,
-                        directives: variable_directives,
-                        r#type: variable_type,
-                    }
-                }).collect();
+    fn convert_fragment_to_core_fragment(
+        &self,
+        py: Python,
+        sources: &SourceIndex,
+        fragment: &Node<Fragment>,
+    ) -> FragmentDefinitionNode {
+        let name = self.get_name_node(py, sources, &fragment.name);
+
+        let type_condition = NamedTypeNode {
+            name: self.get_name_node(py, sources, fragment.type_condition()),
+            loc: sources.location(fragment.type_condition().location()),
+        };
+
+        let directives = fragment.directives.iter().map(|directive| {
+            self.convert_directive_to_core_directive(py, sources, directive)
+        }).collect();
 
-                let selection_set = &operation.selection_set;
+        let selection_set =
+            self.convert_selection_set_to_core_selection_set(py, sources, &fragment.selection_set);
 
-                let selection_set =
-                    self.convert_selection_set_to_core_selection_set(py, selection_set);
+        FragmentDefinitionNode {
+            name,
+            type_condition,
+            directives,
+            // apollo-compiler does not support the experimental fragment variables
+            variable_definitions: vec![],
+            selection_set,
+            loc: sources.location(fragment.location()),
+        }
+    }
 
-                let directives = operation.directives.iter().map(|directive| {
-                    self.convert_directive_to_core_directive(py, directive)
-                }).collect();
+    pub fn source_index(document: &ExecutableDocument, no_location: bool) -> SourceIndex {
+        if no_location {
+            SourceIndex::disabled()
+        } else {
+            SourceIndex::new(&document.sources)
+        }
+    }
 
-                OperationDefinitionNode {
-                    operation: operation_type,
-                    name: operation_name,
-                    variable_definitions,
-                    directives,
-                    selection_set,
-                }
-            })
+    /// Converts the operation together with every fragment it uses, directly or transitively.
+    pub fn convert_operation_with_fragments(
+        &self,
+        py: Python,
+        document: &ExecutableDocument,
+        operation: &Node<Operation>,
+        no_location: bool,
+    ) -> (OperationDefinitionNode, Vec<FragmentDefinitionNode>) {
+        let sources = Self::source_index(document, no_location);
+
+        let fragments = collect_fragments(document, &operation.selection_set)
+            .into_iter()
+            .map(|fragment| self.convert_fragment_to_core_fragment(py, &sources, fragment))
             .collect();
 
+        (self.convert_operation_to_core_operation(py, &sources, operation), fragments)
+    }
+
+    /// Converts all definitions of the document. With `no_location`, no node carries a `loc`,
+    /// mirroring graphql-core's `parse(..., no_location=True)`.
+    pub fn convert_core_to_core_ast(
+        self: &Self,
+        py: Python,
+        document: &ExecutableDocument,
+        no_location: bool,
+    ) -> DocumentNode {
+        let sources = Self::source_index(document, no_location);
+
+        let definitions = definitions_in_source_order(document);
+        let file_id = definitions
+            .iter()
+            .find_map(|definition| definition.location().map(|location| location.file_id()));
+
         DocumentNode {
-            definitions: core_operations,
+            definitions: definitions
+                .into_iter()
+                .map(|definition| match definition {
+                    ExecutableDefinition::Operation(operation) => self
+                        .convert_operation_to_core_operation(py, &sources, operation)
+                        .into_py(py),
+                    ExecutableDefinition::Fragment(fragment) => self
+                        .convert_fragment_to_core_fragment(py, &sources, fragment)
+                        .into_py(py),
+                })
+                .collect(),
+            loc: sources.whole_file(file_id),
         }
     }
 }
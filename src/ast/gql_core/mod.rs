@@ -1,6 +1,7 @@
 //pub mod core_mirror;
 pub mod converter;
 pub mod error;
+pub mod lazy_mirror;
 pub mod location;
 pub(crate) mod mirror_converter;
 pub mod reduced_core_mirror;
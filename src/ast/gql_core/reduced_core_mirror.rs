@@ -1,11 +1,16 @@
 use pyo3::{Py, PyAny};
 use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::ast::gql_core::location::Location;
 
 #[pyclass]
 #[derive(Clone)]
 pub struct NameNode {
     #[pyo3(get)]
-    pub value: String,
+    pub value: Py<PyString>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -26,7 +31,9 @@ impl NameNode {
 #[derive(Clone)]
 pub struct DocumentNode {
     #[pyo3(get)]
-    pub definitions: Vec<OperationDefinitionNode>,
+    pub definitions: Vec<PyObject>, // of type ExecutableDefinitionNode - OperationDefinitionNode, FragmentDefinitionNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -56,6 +63,8 @@ pub struct OperationDefinitionNode {
     pub variable_definitions: Vec<VariableDefinitionNode>,
     #[pyo3(get)]
     pub selection_set: SelectionSetNode,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -72,11 +81,44 @@ impl OperationDefinitionNode {
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+pub struct FragmentDefinitionNode {
+    #[pyo3(get)]
+    pub name: NameNode,
+    #[pyo3(get)]
+    pub type_condition: NamedTypeNode,
+    #[pyo3(get)]
+    pub directives: Vec<DirectiveNode>,
+    #[pyo3(get)]
+    pub variable_definitions: Vec<VariableDefinitionNode>,
+    #[pyo3(get)]
+    pub selection_set: SelectionSetNode,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
+}
+
+#[pymethods]
+impl FragmentDefinitionNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("FragmentDefinitionNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "fragment_definition"
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct SelectionSetNode {
     #[pyo3(get)]
-    pub selections: Vec<FieldNode>,
+    pub selections: Vec<PyObject>, // of type SelectionNode - FieldNode, FragmentSpreadNode, InlineFragmentNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -106,6 +148,8 @@ pub struct FieldNode {
     pub arguments: Vec<ArgumentNode>,
     #[pyo3(get)]
     pub selection_set: Option<SelectionSetNode>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -122,6 +166,58 @@ impl FieldNode {
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+pub struct FragmentSpreadNode {
+    #[pyo3(get)]
+    pub name: NameNode,
+    #[pyo3(get)]
+    pub directives: Vec<DirectiveNode>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
+}
+
+#[pymethods]
+impl FragmentSpreadNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("FragmentSpreadNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "fragment_spread"
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct InlineFragmentNode {
+    #[pyo3(get)]
+    pub type_condition: Option<NamedTypeNode>,
+    #[pyo3(get)]
+    pub directives: Vec<DirectiveNode>,
+    #[pyo3(get)]
+    pub selection_set: SelectionSetNode,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
+}
+
+#[pymethods]
+impl InlineFragmentNode {
+    #[getter(__class__)]
+    pub fn __class__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_node = py.import_bound("graphql.language.ast")?.getattr("InlineFragmentNode")?;
+        Ok(field_node.into())
+    }
+
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        "inline_fragment"
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct DirectiveNode {
@@ -129,6 +225,8 @@ pub struct DirectiveNode {
     pub name: NameNode,
     #[pyo3(get)]
     pub arguments: Vec<ArgumentNode>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -151,7 +249,9 @@ pub struct ArgumentNode {
     #[pyo3(get)]
     pub name: NameNode,
     #[pyo3(get)]
-    pub value: PyObject // of type ValueNode - IntValueNode, FloatValueNode, StringValueNode, BooleanValueNode, EnumValueNode, ListValueNode, ObjectValueNode, NullValueNode
+    pub value: PyObject, // of type ValueNode - IntValueNode, FloatValueNode, StringValueNode, BooleanValueNode, EnumValueNode, ListValueNode, ObjectValueNode, NullValueNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -179,6 +279,8 @@ pub struct VariableDefinitionNode {
     pub default_value: Option<PyObject>, // of type ValueNode - IntValueNode, FloatValueNode, StringValueNode, BooleanValueNode, EnumValueNode, ListValueNode, ObjectValueNode, NullValueNode
     #[pyo3(get)]
     pub directives: Vec<DirectiveNode>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -201,6 +303,8 @@ impl VariableDefinitionNode {
 pub struct NamedTypeNode {
     #[pyo3(get)]
     pub name: NameNode,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -222,6 +326,8 @@ impl NamedTypeNode {
 pub struct ListTypeNode {
     #[pyo3(get)]
     pub r#type: PyObject, // TypeNode - NamedTypeNode, ListTypeNode, NonNullTypeNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -243,6 +349,8 @@ impl ListTypeNode {
 pub struct NonNullTypeNode {
     #[pyo3(get)]
     pub r#type: PyObject, // NamedTypeNode or ListTypeNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -266,6 +374,8 @@ impl NonNullTypeNode {
 pub struct VariableNode {
     #[pyo3(get)]
     pub name: NameNode,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -287,6 +397,8 @@ impl VariableNode {
 pub struct IntValueNode {
     #[pyo3(get)]
     pub value: String,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -308,6 +420,8 @@ impl IntValueNode {
 pub struct FloatValueNode {
     #[pyo3(get)]
     pub value: String,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -331,6 +445,8 @@ pub struct StringValueNode {
     pub value: String,
     #[pyo3(get)]
     pub block: Option<bool>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -352,6 +468,8 @@ impl StringValueNode {
 pub struct BooleanValueNode {
     #[pyo3(get)]
     pub value: bool,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -370,7 +488,10 @@ impl BooleanValueNode {
 
 #[pyclass]
 #[derive(Clone)]
-pub struct NullValueNode {}
+pub struct NullValueNode {
+    #[pyo3(get)]
+    pub loc: Option<Location>,
+}
 
 #[pymethods]
 impl NullValueNode {
@@ -391,6 +512,8 @@ impl NullValueNode {
 pub struct EnumValueNode {
     #[pyo3(get)]
     pub value: String,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -412,6 +535,8 @@ impl EnumValueNode {
 pub struct ListValueNode {
     #[pyo3(get)]
     pub values: Vec<PyObject>, //of type ValueNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -435,6 +560,8 @@ pub struct ObjectFieldNode {
     pub name: NameNode,
     #[pyo3(get)]
     pub value: PyObject, //of type ValueNode
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -456,6 +583,8 @@ impl ObjectFieldNode {
 pub struct ObjectValueNode {
     #[pyo3(get)]
     pub fields: Vec<ObjectFieldNode>,
+    #[pyo3(get)]
+    pub loc: Option<Location>,
 }
 
 #[pymethods]
@@ -1,2 +1,3 @@
 pub mod gql_core;
-pub mod strawberry;
\ No newline at end of file
+pub mod operation;
+pub mod strawberry;
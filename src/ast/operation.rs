@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use apollo_compiler::executable::{Fragment, Operation, Selection, SelectionSet};
+use apollo_compiler::{ExecutableDocument, Node};
+
+/// Selects the operation to execute, following the GetOperation algorithm of the spec.
+/// On failure, returns the graphql-core compatible error message.
+pub fn get_operation<'doc>(
+    document: &'doc ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Result<&'doc Node<Operation>, String> {
+    match operation_name {
+        Some(operation_name) => document
+            .operations
+            .get(Some(operation_name))
+            .map_err(|_| format!("Unknown operation named '{operation_name}'.")),
+        None => match document.all_operations().count() {
+            0 => Err("Must provide an operation.".to_string()),
+            1 => document
+                .operations
+                .get(None)
+                .map_err(|_| "Must provide an operation.".to_string()),
+            _ => Err(
+                "Must provide operation name if query contains multiple operations.".to_string(),
+            ),
+        },
+    }
+}
+
+/// All fragments spread by the selection set, directly or through other fragments,
+/// in the order they are first encountered. Unknown fragments are skipped.
+pub fn collect_fragments<'doc>(
+    document: &'doc ExecutableDocument,
+    selection_set: &SelectionSet,
+) -> Vec<&'doc Node<Fragment>> {
+    let mut visited = HashSet::new();
+    let mut fragments = Vec::new();
+    collect_fragments_into(document, selection_set, &mut visited, &mut fragments);
+    fragments
+}
+
+fn collect_fragments_into<'doc>(
+    document: &'doc ExecutableDocument,
+    selection_set: &SelectionSet,
+    visited: &mut HashSet<&'doc str>,
+    fragments: &mut Vec<&'doc Node<Fragment>>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                collect_fragments_into(document, &field.selection_set, visited, fragments)
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                collect_fragments_into(document, &inline_fragment.selection_set, visited, fragments)
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                let Some(fragment) = document.fragments.get(&fragment_spread.fragment_name) else {
+                    continue;
+                };
+                if visited.insert(fragment.name.as_str()) {
+                    fragments.push(fragment);
+                    collect_fragments_into(document, &fragment.selection_set, visited, fragments);
+                }
+            }
+        }
+    }
+}
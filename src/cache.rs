@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use apollo_compiler::ExecutableDocument;
+use pyo3::prelude::*;
+
+use crate::ast::gql_core::error::GraphQLError;
+use crate::ast::gql_core::reduced_core_mirror::DocumentNode;
+
+/// Identifies the cache entry of a source text. Keys compare the full source name and text,
+/// so a document only ever sees the entry of its own source.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    source_name: Arc<str>,
+    source: Arc<str>,
+}
+
+impl CacheKey {
+    pub fn new(source_name: &str, source: &str) -> Self {
+        Self {
+            source_name: source_name.into(),
+            source: source.into(),
+        }
+    }
+}
+
+/// Everything `QueryCompiler` knows about a previously parsed source text.
+pub struct CacheEntry {
+    pub document: Arc<ExecutableDocument>,
+    /// Errors of validating with the compiler's default options, once validated.
+    pub validation_errors: Option<Vec<GraphQLError>>,
+    /// The mirror AST with locations, once converted and if AST caching is enabled.
+    pub mirror_ast: Option<Py<DocumentNode>>,
+}
+
+/// Statistics of the parse cache, like `functools.lru_cache`'s `cache_info()`.
+#[pyclass(frozen)]
+pub struct CacheInfo {
+    #[pyo3(get)]
+    hits: usize,
+    #[pyo3(get)]
+    misses: usize,
+    #[pyo3(get)]
+    maxsize: usize,
+    #[pyo3(get)]
+    currsize: usize,
+}
+
+#[pymethods]
+impl CacheInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "CacheInfo(hits={}, misses={}, maxsize={}, currsize={})",
+            self.hits, self.misses, self.maxsize, self.currsize
+        )
+    }
+}
+
+/// Least recently used cache of parsed documents, keyed by source name and text.
+/// A capacity of 0 disables caching.
+pub struct DocumentCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, (CacheEntry, u64)>,
+    /// Last use stamp to key, the first entry is the least recently used one.
+    recency: BTreeMap<u64, CacheKey>,
+    next_stamp: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl DocumentCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_stamp: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Looks up the entry for `key`, marking it as recently used and counting a hit or miss.
+    pub fn get(&mut self, key: &CacheKey) -> Option<&mut CacheEntry> {
+        let stamp = self.next_stamp;
+        match self.entries.get_mut(key) {
+            Some((entry, last_used)) => {
+                self.recency.remove(last_used);
+                self.recency.insert(stamp, key.clone());
+                *last_used = stamp;
+                self.next_stamp += 1;
+                self.hits += 1;
+                Some(entry)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// The entry for `key` without affecting recency or statistics.
+    pub fn peek_mut(&mut self, key: &CacheKey) -> Option<&mut CacheEntry> {
+        self.entries.get_mut(key).map(|(entry, _)| entry)
+    }
+
+    pub fn insert(&mut self, key: CacheKey, document: Arc<ExecutableDocument>) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Some((_, last_used)) = self.entries.remove(&key) {
+            self.recency.remove(&last_used);
+        }
+        while self.entries.len() >= self.capacity {
+            let Some((_, evicted)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&evicted);
+        }
+
+        let entry = CacheEntry {
+            document,
+            validation_errors: None,
+            mirror_ast: None,
+        };
+        self.entries.insert(key, (entry, self.next_stamp));
+        self.recency.insert(self.next_stamp, key);
+        self.next_stamp += 1;
+    }
+
+    pub fn info(&self) -> CacheInfo {
+        CacheInfo {
+            hits: self.hits,
+            misses: self.misses,
+            maxsize: self.capacity,
+            currsize: self.entries.len(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
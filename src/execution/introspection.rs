@@ -0,0 +1,553 @@
+use apollo_compiler::executable::{
+    DirectiveList as ExecutableDirectiveList, Field, Operation, OperationType, Selection,
+    SelectionSet,
+};
+use apollo_compiler::schema::{
+    DirectiveDefinition, DirectiveList, EnumValueDefinition, ExtendedType, FieldDefinition,
+    InputValueDefinition, Type, Value,
+};
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{ExecutableDocument, Name, Node, Schema};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::ast::gql_core::error::GraphQLError;
+use crate::execution::variables::const_value_to_py;
+
+/// An object of the introspection schema, or the root object of the operation.
+enum Introspected<'a> {
+    Root(&'a Name),
+    Schema,
+    Type(IntrospectedType<'a>),
+    Field(&'a FieldDefinition),
+    InputValue(&'a InputValueDefinition),
+    EnumValue(&'a EnumValueDefinition),
+    Directive(&'a DirectiveDefinition),
+}
+
+impl Introspected<'_> {
+    fn type_name(&self) -> &str {
+        match self {
+            Introspected::Root(type_name) => type_name.as_str(),
+            Introspected::Schema => "__Schema",
+            Introspected::Type(_) => "__Type",
+            Introspected::Field(_) => "__Field",
+            Introspected::InputValue(_) => "__InputValue",
+            Introspected::EnumValue(_) => "__EnumValue",
+            Introspected::Directive(_) => "__Directive",
+        }
+    }
+}
+
+/// A `__Type`. Wrapping types hold the type they wrap, which is their `ofType`.
+enum IntrospectedType<'a> {
+    Named(&'a ExtendedType),
+    List(Type),
+    NonNull(Type),
+}
+
+enum Resolved<'a> {
+    Value(PyObject),
+    Object(Introspected<'a>),
+    List(Vec<Introspected<'a>>),
+}
+
+fn type_name(ty: &ExtendedType) -> &Name {
+    match ty {
+        ExtendedType::Scalar(scalar) => &scalar.name,
+        ExtendedType::Object(object) => &object.name,
+        ExtendedType::Interface(interface) => &interface.name,
+        ExtendedType::Union(union_type) => &union_type.name,
+        ExtendedType::Enum(enum_type) => &enum_type.name,
+        ExtendedType::InputObject(input_object) => &input_object.name,
+    }
+}
+
+fn type_description(ty: &ExtendedType) -> Option<&str> {
+    match ty {
+        ExtendedType::Scalar(scalar) => scalar.description.as_deref(),
+        ExtendedType::Object(object) => object.description.as_deref(),
+        ExtendedType::Interface(interface) => interface.description.as_deref(),
+        ExtendedType::Union(union_type) => union_type.description.as_deref(),
+        ExtendedType::Enum(enum_type) => enum_type.description.as_deref(),
+        ExtendedType::InputObject(input_object) => input_object.description.as_deref(),
+    }
+}
+
+fn deprecation_reason(directives: &DirectiveList) -> Option<&str> {
+    directives.get("deprecated").map(|deprecated| {
+        deprecated
+            .argument_by_name("reason")
+            .and_then(|reason| reason.as_str())
+            .unwrap_or("No longer supported")
+    })
+}
+
+struct Executor<'a, 'py> {
+    py: Python<'py>,
+    schema: &'a Valid<Schema>,
+    document: &'a ExecutableDocument,
+    variables: &'a Bound<'py, PyDict>,
+    errors: Vec<GraphQLError>,
+}
+
+impl<'a, 'py> Executor<'a, 'py> {
+    fn named_type(&self, name: &str) -> Option<Introspected<'a>> {
+        let ty = self.schema.types.get(name)?;
+        Some(Introspected::Type(IntrospectedType::Named(ty)))
+    }
+
+    fn type_reference(&self, ty: &Type) -> Option<Introspected<'a>> {
+        match ty {
+            Type::Named(name) => self.named_type(name),
+            Type::NonNullNamed(name) => Some(Introspected::Type(IntrospectedType::NonNull(
+                Type::Named(name.clone()),
+            ))),
+            Type::List(item_type) => Some(Introspected::Type(IntrospectedType::List(
+                item_type.as_ref().clone(),
+            ))),
+            Type::NonNullList(item_type) => Some(Introspected::Type(IntrospectedType::NonNull(
+                Type::List(item_type.clone()),
+            ))),
+        }
+    }
+
+    fn argument(&self, field: &Field, name: &str) -> Option<Bound<'py, PyAny>> {
+        let argument = field
+            .arguments
+            .iter()
+            .find(|argument| argument.name.as_str() == name)?;
+        match &*argument.value {
+            Value::Variable(variable) => self
+                .variables
+                .get_item(variable.as_str())
+                .ok()
+                .flatten(),
+            _ => Some(const_value_to_py(self.py, &argument.value).into_bound(self.py)),
+        }
+    }
+
+    fn include_deprecated(&self, field: &Field) -> bool {
+        self.argument(field, "includeDeprecated")
+            .and_then(|value| value.extract::<bool>().ok())
+            .unwrap_or(false)
+    }
+
+    /// Whether `@skip` and `@include` keep the selection, as described by CollectFields.
+    fn is_included(&self, directives: &ExecutableDirectiveList) -> bool {
+        let condition = |directive_name: &str| {
+            directives
+                .get(directive_name)
+                .and_then(|directive| directive.argument_by_name("if"))
+                .map(|value| match &**value {
+                    Value::Variable(variable) => self
+                        .variables
+                        .get_item(variable.as_str())
+                        .ok()
+                        .flatten()
+                        .and_then(|value| value.extract::<bool>().ok())
+                        .unwrap_or(false),
+                    Value::Boolean(boolean) => *boolean,
+                    _ => false,
+                })
+        };
+        condition("skip") != Some(true) && condition("include") != Some(false)
+    }
+
+    fn does_fragment_type_apply(&self, object_type: &str, type_condition: &str) -> bool {
+        object_type == type_condition || self.schema.is_subtype(type_condition, object_type)
+    }
+
+    /// Groups the fields of the selection sets by response key, in order.
+    fn collect_fields(
+        &self,
+        object_type: &str,
+        selection_sets: &[&'a SelectionSet],
+        visited_fragments: &mut Vec<&'a Name>,
+        fields: &mut Vec<(&'a Name, Vec<&'a Node<Field>>)>,
+    ) {
+        for selection_set in selection_sets.iter().copied() {
+            for selection in &selection_set.selections {
+                match selection {
+                    Selection::Field(field) => {
+                        if !self.is_included(&field.directives) {
+                            continue;
+                        }
+                        let response_key = field.response_key();
+                        match fields.iter_mut().find(|(key, _)| *key == response_key) {
+                            Some((_, group)) => group.push(field),
+                            None => fields.push((response_key, vec![field])),
+                        }
+                    }
+                    Selection::FragmentSpread(fragment_spread) => {
+                        let name = &fragment_spread.fragment_name;
+                        if !self.is_included(&fragment_spread.directives)
+                            || visited_fragments.contains(&name)
+                        {
+                            continue;
+                        }
+                        visited_fragments.push(name);
+                        let Some(fragment) = self.document.fragments.get(name) else {
+                            continue;
+                        };
+                        if self.does_fragment_type_apply(object_type, fragment.type_condition()) {
+                            self.collect_fields(
+                                object_type,
+                                &[&fragment.selection_set],
+                                visited_fragments,
+                                fields,
+                            );
+                        }
+                    }
+                    Selection::InlineFragment(inline_fragment) => {
+                        let applies = inline_fragment
+                            .type_condition
+                            .as_ref()
+                            .map_or(true, |condition| {
+                                self.does_fragment_type_apply(object_type, condition)
+                            });
+                        if applies && self.is_included(&inline_fragment.directives) {
+                            self.collect_fields(
+                                object_type,
+                                &[&inline_fragment.selection_set],
+                                visited_fragments,
+                                fields,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn execute_selection_sets(
+        &mut self,
+        object: &Introspected<'a>,
+        selection_sets: &[&'a SelectionSet],
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let mut fields = Vec::new();
+        self.collect_fields(object.type_name(), selection_sets, &mut vec![], &mut fields);
+
+        let data = PyDict::new_bound(self.py);
+        for (response_key, field_group) in fields {
+            let field = field_group[0];
+            let sub_selection_sets: Vec<&'a SelectionSet> =
+                field_group.iter().map(|field| &field.selection_set).collect();
+
+            let value = match self.resolve(object, field)? {
+                Resolved::Value(value) => value,
+                Resolved::Object(child) => {
+                    self.execute_selection_sets(&child, &sub_selection_sets)?.into_py(self.py)
+                }
+                Resolved::List(children) => {
+                    let items = PyList::empty_bound(self.py);
+                    for child in &children {
+                        items.append(self.execute_selection_sets(child, &sub_selection_sets)?)?;
+                    }
+                    items.into_py(self.py)
+                }
+            };
+            data.set_item(response_key.as_str(), value)?;
+        }
+        Ok(data)
+    }
+
+    fn resolve(&mut self, object: &Introspected<'a>, field: &'a Node<Field>) -> PyResult<Resolved<'a>> {
+        let py = self.py;
+        let value = |value: PyObject| Ok(Resolved::Value(value));
+        let optional_object = |object: Option<Introspected<'a>>| {
+            Ok(object.map_or_else(|| Resolved::Value(py.None()), Resolved::Object))
+        };
+
+        if field.name == "__typename" {
+            return value(object.type_name().into_py(py));
+        }
+
+        match object {
+            Introspected::Root(_) => match field.name.as_str() {
+                "__schema" => Ok(Resolved::Object(Introspected::Schema)),
+                "__type" => {
+                    let name = self
+                        .argument(field, "name")
+                        .and_then(|name| name.extract::<String>().ok());
+                    optional_object(name.and_then(|name| self.named_type(&name)))
+                }
+                _ => {
+                    self.errors.push(GraphQLError::new(
+                        format!("Field '{}' cannot be resolved by introspection.", field.name),
+                        field.location(),
+                        &self.document.sources,
+                        None,
+                    ));
+                    value(py.None())
+                }
+            },
+            Introspected::Schema => {
+                let schema = self.schema;
+                match field.name.as_str() {
+                    "description" => {
+                        value(schema.schema_definition.description.as_deref().into_py(py))
+                    }
+                    "types" => Ok(Resolved::List(
+                        schema
+                            .types
+                            .values()
+                            .map(|ty| Introspected::Type(IntrospectedType::Named(ty)))
+                            .collect(),
+                    )),
+                    "queryType" | "mutationType" | "subscriptionType" => {
+                        let operation_type = match field.name.as_str() {
+                            "queryType" => OperationType::Query,
+                            "mutationType" => OperationType::Mutation,
+                            _ => OperationType::Subscription,
+                        };
+                        optional_object(
+                            schema
+                                .root_operation(operation_type)
+                                .and_then(|name| self.named_type(name)),
+                        )
+                    }
+                    "directives" => Ok(Resolved::List(
+                        schema
+                            .directive_definitions
+                            .values()
+                            .map(|directive| Introspected::Directive(directive))
+                            .collect(),
+                    )),
+                    _ => value(py.None()),
+                }
+            }
+            Introspected::Type(ty) => self.resolve_type(ty, field),
+            Introspected::Field(definition) => match field.name.as_str() {
+                "name" => value(definition.name.as_str().into_py(py)),
+                "description" => value(definition.description.as_deref().into_py(py)),
+                "args" => {
+                    let include_deprecated = self.include_deprecated(field);
+                    Ok(Resolved::List(
+                        definition
+                            .arguments
+                            .iter()
+                            .filter(|argument| {
+                                include_deprecated || deprecation_reason(&argument.directives).is_none()
+                            })
+                            .map(|argument| Introspected::InputValue(argument))
+                            .collect(),
+                    ))
+                }
+                "type" => optional_object(self.type_reference(&definition.ty)),
+                "isDeprecated" => value(deprecation_reason(&definition.directives).is_some().into_py(py)),
+                "deprecationReason" => value(deprecation_reason(&definition.directives).into_py(py)),
+                _ => value(py.None()),
+            },
+            Introspected::InputValue(definition) => match field.name.as_str() {
+                "name" => value(definition.name.as_str().into_py(py)),
+                "description" => value(definition.description.as_deref().into_py(py)),
+                "type" => optional_object(self.type_reference(&definition.ty)),
+                "defaultValue" => value(
+                    definition
+                        .default_value
+                        .as_ref()
+                        .map(|default_value| default_value.to_string())
+                        .into_py(py),
+                ),
+                "isDeprecated" => value(deprecation_reason(&definition.directives).is_some().into_py(py)),
+                "deprecationReason" => value(deprecation_reason(&definition.directives).into_py(py)),
+                _ => value(py.None()),
+            },
+            Introspected::EnumValue(definition) => match field.name.as_str() {
+                "name" => value(definition.value.as_str().into_py(py)),
+                "description" => value(definition.description.as_deref().into_py(py)),
+                "isDeprecated" => value(deprecation_reason(&definition.directives).is_some().into_py(py)),
+                "deprecationReason" => value(deprecation_reason(&definition.directives).into_py(py)),
+                _ => value(py.None()),
+            },
+            Introspected::Directive(definition) => match field.name.as_str() {
+                "name" => value(definition.name.as_str().into_py(py)),
+                "description" => value(definition.description.as_deref().into_py(py)),
+                "isRepeatable" => value(definition.repeatable.into_py(py)),
+                "locations" => {
+                    let locations: Vec<String> =
+                        definition.locations.iter().map(|location| location.to_string()).collect();
+                    value(locations.into_py(py))
+                }
+                "args" => {
+                    let include_deprecated = self.include_deprecated(field);
+                    Ok(Resolved::List(
+                        definition
+                            .arguments
+                            .iter()
+                            .filter(|argument| {
+                                include_deprecated || deprecation_reason(&argument.directives).is_none()
+                            })
+                            .map(|argument| Introspected::InputValue(argument))
+                            .collect(),
+                    ))
+                }
+                _ => value(py.None()),
+            },
+        }
+    }
+
+    fn resolve_type(&self, ty: &IntrospectedType<'a>, field: &'a Node<Field>) -> PyResult<Resolved<'a>> {
+        let py = self.py;
+        let none = || Ok(Resolved::Value(py.None()));
+
+        let named = match ty {
+            IntrospectedType::Named(named) => *named,
+            IntrospectedType::List(of_type) | IntrospectedType::NonNull(of_type) => {
+                return match field.name.as_str() {
+                    "kind" => {
+                        let kind = match ty {
+                            IntrospectedType::List(_) => "LIST",
+                            _ => "NON_NULL",
+                        };
+                        Ok(Resolved::Value(kind.into_py(py)))
+                    }
+                    "ofType" => Ok(self
+                        .type_reference(of_type)
+                        .map_or_else(|| Resolved::Value(py.None()), Resolved::Object)),
+                    _ => none(),
+                };
+            }
+        };
+
+        let include_deprecated = || self.include_deprecated(field);
+        let fields = |fields: Vec<&'a FieldDefinition>| {
+            let include_deprecated = include_deprecated();
+            Ok(Resolved::List(
+                fields
+                    .into_iter()
+                    .filter(|field| include_deprecated || deprecation_reason(&field.directives).is_none())
+                    .map(Introspected::Field)
+                    .collect(),
+            ))
+        };
+        let named_types = |names: Vec<&Name>| {
+            Ok(Resolved::List(names.into_iter().filter_map(|name| self.named_type(name)).collect()))
+        };
+
+        match (field.name.as_str(), named) {
+            ("kind", _) => {
+                let kind = match named {
+                    ExtendedType::Scalar(_) => "SCALAR",
+                    ExtendedType::Object(_) => "OBJECT",
+                    ExtendedType::Interface(_) => "INTERFACE",
+                    ExtendedType::Union(_) => "UNION",
+                    ExtendedType::Enum(_) => "ENUM",
+                    ExtendedType::InputObject(_) => "INPUT_OBJECT",
+                };
+                Ok(Resolved::Value(kind.into_py(py)))
+            }
+            ("name", _) => Ok(Resolved::Value(type_name(named).as_str().into_py(py))),
+            ("description", _) => Ok(Resolved::Value(type_description(named).into_py(py))),
+            ("fields", ExtendedType::Object(object)) => {
+                fields(object.fields.values().map(|field| &**field).collect())
+            }
+            ("fields", ExtendedType::Interface(interface)) => {
+                fields(interface.fields.values().map(|field| &**field).collect())
+            }
+            ("interfaces", ExtendedType::Object(object)) => {
+                named_types(object.implements_interfaces.iter().map(|name| &name.name).collect())
+            }
+            ("interfaces", ExtendedType::Interface(interface)) => {
+                named_types(interface.implements_interfaces.iter().map(|name| &name.name).collect())
+            }
+            ("possibleTypes", ExtendedType::Interface(interface)) => named_types(
+                self.schema
+                    .types
+                    .values()
+                    .filter_map(|ty| match ty {
+                        ExtendedType::Object(object)
+                            if object
+                                .implements_interfaces
+                                .iter()
+                                .any(|name| name.name == interface.name) =>
+                        {
+                            Some(&object.name)
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            ("possibleTypes", ExtendedType::Union(union_type)) => {
+                named_types(union_type.members.iter().map(|name| &name.name).collect())
+            }
+            ("enumValues", ExtendedType::Enum(enum_type)) => {
+                let include_deprecated = include_deprecated();
+                Ok(Resolved::List(
+                    enum_type
+                        .values
+                        .values()
+                        .filter(|value| include_deprecated || deprecation_reason(&value.directives).is_none())
+                        .map(|value| Introspected::EnumValue(value))
+                        .collect(),
+                ))
+            }
+            ("inputFields", ExtendedType::InputObject(input_object)) => {
+                let include_deprecated = include_deprecated();
+                Ok(Resolved::List(
+                    input_object
+                        .fields
+                        .values()
+                        .filter(|field| include_deprecated || deprecation_reason(&field.directives).is_none())
+                        .map(|field| Introspected::InputValue(field))
+                        .collect(),
+                ))
+            }
+            ("specifiedByURL", ExtendedType::Scalar(scalar)) => {
+                let url = scalar
+                    .directives
+                    .get("specifiedBy")
+                    .and_then(|directive| directive.argument_by_name("url"))
+                    .and_then(|url| url.as_str());
+                Ok(Resolved::Value(url.into_py(py)))
+            }
+            ("isOneOf", ExtendedType::InputObject(input_object)) => {
+                Ok(Resolved::Value(input_object.directives.get("oneOf").is_some().into_py(py)))
+            }
+            _ => none(),
+        }
+    }
+}
+
+/// Executes the introspection meta-fields `__schema`, `__type` and `__typename` of the
+/// operation from the schema alone. Other root fields resolve to null with an error.
+/// Returns the data and errors like graphql-core's `ExecutionResult.formatted`.
+pub fn execute_introspection<'py>(
+    py: Python<'py>,
+    schema: &Valid<Schema>,
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+    variables: &Bound<'py, PyDict>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut executor = Executor {
+        py,
+        schema,
+        document,
+        variables,
+        errors: Vec::new(),
+    };
+
+    let root_type = &operation.selection_set.ty;
+    let data =
+        executor.execute_selection_sets(&Introspected::Root(root_type), &[&operation.selection_set])?;
+    execution_result(py, Some(data.into_any()), &executor.errors)
+}
+
+/// Builds a result dict in the shape of graphql-core's `ExecutionResult.formatted`.
+pub fn execution_result<'py>(
+    py: Python<'py>,
+    data: Option<Bound<'py, PyAny>>,
+    errors: &[GraphQLError],
+) -> PyResult<Bound<'py, PyDict>> {
+    let result = PyDict::new_bound(py);
+    result.set_item("data", data)?;
+    if !errors.is_empty() {
+        let errors = errors
+            .iter()
+            .map(|error| error.formatted(py))
+            .collect::<PyResult<Vec<_>>>()?;
+        result.set_item("errors", errors)?;
+    }
+    Ok(result)
+}
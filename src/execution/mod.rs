@@ -0,0 +1,2 @@
+pub mod introspection;
+pub mod variables;
@@ -0,0 +1,329 @@
+use std::ops::Deref;
+
+use apollo_compiler::executable::Operation;
+use apollo_compiler::schema::{ExtendedType, Type, Value};
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{ExecutableDocument, Node, Schema};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PyLong, PyMapping, PyString, PyTuple};
+
+use crate::ast::gql_core::error::GraphQLError;
+
+/// A segment of the path to an invalid value inside a variable, e.g. `input.items[0]`.
+#[derive(Clone)]
+enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+fn format_path(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Field(name) => format!(".{name}"),
+            PathSegment::Index(index) => format!("[{index}]"),
+        })
+        .collect()
+}
+
+/// ` at 'name.path'` locating an invalid value inside the variable, empty for the variable itself.
+fn at_path(name: &str, path: &[PathSegment]) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!(" at '{name}{}'", format_path(path))
+    }
+}
+
+/// Coerces the variables of `operation` as described by CoerceVariableValues in the spec.
+/// Error messages match graphql-core's `get_variable_values`.
+pub fn coerce_variable_values<'py>(
+    py: Python<'py>,
+    schema: &Valid<Schema>,
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+    values: &Bound<'py, PyDict>,
+) -> PyResult<Result<Bound<'py, PyDict>, Vec<GraphQLError>>> {
+    let coerced = PyDict::new_bound(py);
+    let mut errors = Vec::new();
+
+    for variable in &operation.variables {
+        let name = variable.name.as_str();
+        let ty = variable.ty.deref();
+        let variable_error = |message: String| {
+            GraphQLError::new(message, variable.location(), &document.sources, None)
+        };
+
+        let Some(value) = values.get_item(name)? else {
+            if let Some(default_value) = &variable.default_value {
+                let default_value = const_value_to_py(py, default_value);
+                let mut default_errors = Vec::new();
+                let coerced_default = coerce_input_value(
+                    py,
+                    schema,
+                    default_value.bind(py),
+                    ty,
+                    &mut vec![],
+                    &mut default_errors,
+                )?;
+                if default_errors.is_empty() {
+                    coerced.set_item(name, coerced_default)?;
+                }
+                for (path, invalid_value, message) in default_errors {
+                    errors.push(variable_error(format!(
+                        "Variable '${name}' has invalid default value {}{}; {message}",
+                        invalid_value.bind(py).repr()?,
+                        at_path(name, &path)
+                    )));
+                }
+            } else if ty.is_non_null() {
+                errors.push(variable_error(format!(
+                    "Variable '${name}' of required type '{ty}' was not provided."
+                )));
+            }
+            continue;
+        };
+
+        if value.is_none() && ty.is_non_null() {
+            errors.push(variable_error(format!(
+                "Variable '${name}' of non-null type '{ty}' must not be null."
+            )));
+            continue;
+        }
+
+        let mut value_errors = Vec::new();
+        let coerced_value =
+            coerce_input_value(py, schema, &value, ty, &mut vec![], &mut value_errors)?;
+        if value_errors.is_empty() {
+            coerced.set_item(name, coerced_value)?;
+        }
+        for (path, invalid_value, message) in value_errors {
+            errors.push(variable_error(format!(
+                "Variable '${name}' got invalid value {}{}; {message}",
+                invalid_value.bind(py).repr()?,
+                at_path(name, &path)
+            )));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(Ok(coerced))
+    } else {
+        Ok(Err(errors))
+    }
+}
+
+type CoercionError = (Vec<PathSegment>, PyObject, String);
+
+fn report(
+    errors: &mut Vec<CoercionError>,
+    path: &[PathSegment],
+    value: &Bound<'_, PyAny>,
+    message: String,
+) {
+    errors.push((path.to_vec(), value.clone().unbind(), message));
+}
+
+/// Coerces a single input value, collecting `(path, invalid value, message)` triples.
+/// The returned value is only meaningful if no error was collected.
+fn coerce_input_value(
+    py: Python<'_>,
+    schema: &Valid<Schema>,
+    value: &Bound<'_, PyAny>,
+    ty: &Type,
+    path: &mut Vec<PathSegment>,
+    errors: &mut Vec<CoercionError>,
+) -> PyResult<PyObject> {
+    if value.is_none() {
+        if ty.is_non_null() {
+            let message = format!("Expected non-nullable type '{ty}' not to be null.");
+            report(errors, path, value, message);
+        }
+        return Ok(py.None());
+    }
+
+    match ty {
+        Type::List(item_type) | Type::NonNullList(item_type) => {
+            let is_sequence =
+                value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>();
+            if !is_sequence {
+                // A single value is coerced into a list of one
+                let item = coerce_input_value(py, schema, value, item_type, path, errors)?;
+                return Ok(PyList::new_bound(py, [item]).into_py(py));
+            }
+            let items = PyList::empty_bound(py);
+            for (index, item) in value.iter()?.enumerate() {
+                path.push(PathSegment::Index(index));
+                items.append(coerce_input_value(py, schema, &item?, item_type, path, errors)?)?;
+                path.pop();
+            }
+            Ok(items.into_py(py))
+        }
+        Type::Named(type_name) | Type::NonNullNamed(type_name) => {
+            match schema.types.get(type_name) {
+                Some(ExtendedType::Scalar(_)) => {
+                    match coerce_scalar(py, type_name.as_str(), value)? {
+                        Ok(coerced) => Ok(coerced),
+                        Err(message) => {
+                            report(errors, path, value, message);
+                            Ok(py.None())
+                        }
+                    }
+                }
+                Some(ExtendedType::Enum(enum_type)) => {
+                    let Ok(enum_value) = value.downcast::<PyString>() else {
+                        let message = format!(
+                            "Enum '{type_name}' cannot represent non-string value: {}.",
+                            value.repr()?
+                        );
+                        report(errors, path, value, message);
+                        return Ok(py.None());
+                    };
+                    let enum_value = enum_value.to_str()?;
+                    if !enum_type.values.contains_key(enum_value) {
+                        let message =
+                            format!("Value '{enum_value}' does not exist in '{type_name}' enum.");
+                        report(errors, path, value, message);
+                    }
+                    Ok(value.clone().unbind())
+                }
+                Some(ExtendedType::InputObject(input_object)) => {
+                    let Ok(fields) = value.downcast::<PyMapping>() else {
+                        report(errors, path, value, format!("Expected type '{type_name}' to be a mapping."));
+                        return Ok(py.None());
+                    };
+                    let coerced = PyDict::new_bound(py);
+                    for (field_name, field_definition) in &input_object.fields {
+                        let field_value = if fields.contains(field_name.as_str())? {
+                            Some(fields.get_item(field_name.as_str())?)
+                        } else {
+                            field_definition
+                                .default_value
+                                .as_ref()
+                                .map(|default_value| const_value_to_py(py, default_value).into_bound(py))
+                        };
+                        match field_value {
+                            Some(field_value) => {
+                                path.push(PathSegment::Field(field_name.to_string()));
+                                let coerced_field = coerce_input_value(
+                                    py,
+                                    schema,
+                                    &field_value,
+                                    &field_definition.ty,
+                                    path,
+                                    errors,
+                                )?;
+                                path.pop();
+                                coerced.set_item(field_name.as_str(), coerced_field)?;
+                            }
+                            None => {
+                                if field_definition.ty.is_non_null() {
+                                    report(errors, path, value, format!(
+                                        "Field '{field_name}' of required type '{}' was not provided.",
+                                        field_definition.ty
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    for key in fields.keys()?.iter()? {
+                        let key = key?.str()?;
+                        if !input_object.fields.contains_key(key.to_str()?) {
+                            let message = format!("Field '{key}' is not defined by type '{type_name}'.");
+                            report(errors, path, value, message);
+                        }
+                    }
+                    Ok(coerced.into_py(py))
+                }
+                _ => {
+                    report(errors, path, value, format!("Type '{type_name}' is not an input type."));
+                    Ok(py.None())
+                }
+            }
+        }
+    }
+}
+
+/// Coerces a value for a built-in scalar. Custom scalars are passed through unchanged.
+fn coerce_scalar(
+    py: Python<'_>,
+    type_name: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<Result<PyObject, String>> {
+    let is_bool = value.is_instance_of::<PyBool>();
+    let is_int = value.is_instance_of::<PyLong>() && !is_bool;
+    let is_float = value.is_instance_of::<PyFloat>();
+
+    let coerced = match type_name {
+        "Int" => {
+            let integral = if is_int {
+                value.extract::<i64>().ok()
+            } else if is_float {
+                let float = value.extract::<f64>()?;
+                (float.fract() == 0.0 && float.is_finite()).then_some(float as i64)
+            } else {
+                None
+            };
+            match integral {
+                Some(int) if i32::try_from(int).is_ok() => Ok(int.into_py(py)),
+                Some(_) => Err(format!(
+                    "Int cannot represent non 32-bit signed integer value: {}",
+                    value.repr()?
+                )),
+                None if is_int => Err(format!(
+                    "Int cannot represent non 32-bit signed integer value: {}",
+                    value.repr()?
+                )),
+                None => Err(format!("Int cannot represent non-integer value: {}", value.repr()?)),
+            }
+        }
+        "Float" if is_int || is_float => {
+            let float = value.extract::<f64>()?;
+            if float.is_finite() {
+                Ok(float.into_py(py))
+            } else {
+                Err(format!("Float cannot represent non numeric value: {}", value.repr()?))
+            }
+        }
+        "Float" => Err(format!("Float cannot represent non numeric value: {}", value.repr()?)),
+        "String" if value.is_instance_of::<PyString>() => Ok(value.clone().unbind()),
+        "String" => Err(format!("String cannot represent a non string value: {}", value.repr()?)),
+        "Boolean" if is_bool => Ok(value.clone().unbind()),
+        "Boolean" => Err(format!("Boolean cannot represent a non boolean value: {}", value.repr()?)),
+        "ID" if value.is_instance_of::<PyString>() => Ok(value.clone().unbind()),
+        "ID" if is_int => Ok(value.str()?.into_py(py)),
+        "ID" => Err(format!("ID cannot represent value: {}", value.repr()?)),
+        _ => Ok(value.clone().unbind()),
+    };
+    Ok(coerced)
+}
+
+/// Converts a constant AST value, such as a default value, to its Python equivalent.
+pub fn const_value_to_py(py: Python<'_>, value: &Node<Value>) -> PyObject {
+    match value.deref() {
+        Value::Null | Value::Variable(_) => py.None(),
+        Value::Enum(name) => name.as_str().into_py(py),
+        Value::String(string) => string.as_str().into_py(py),
+        Value::Boolean(boolean) => boolean.into_py(py),
+        Value::Int(int) => match int.as_str().parse::<i64>() {
+            Ok(int) => int.into_py(py),
+            Err(_) => int.as_str().into_py(py),
+        },
+        Value::Float(float) => match float.as_str().parse::<f64>() {
+            Ok(float) => float.into_py(py),
+            Err(_) => float.as_str().into_py(py),
+        },
+        Value::List(values) => {
+            let values: Vec<PyObject> =
+                values.iter().map(|value| const_value_to_py(py, value)).collect();
+            values.into_py(py)
+        }
+        Value::Object(fields) => {
+            let object = PyDict::new_bound(py);
+            for (name, value) in fields {
+                // Setting items on a fresh dict with str keys cannot fail
+                let _ = object.set_item(name.as_str(), const_value_to_py(py, value));
+            }
+            object.into_py(py)
+        }
+    }
+}
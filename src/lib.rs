@@ -1,105 +1,508 @@
 //use pyo3::wrap_pyfunction;
 extern crate apollo_compiler;
 
-use apollo_compiler::{ExecutableDocument, Schema};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use apollo_compiler::{ExecutableDocument, Node, Schema};
+use apollo_compiler::executable::{Operation, OperationType};
 use apollo_compiler::validation::Valid;
 use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyType};
+use rayon::prelude::*;
 
 use crate::ast::gql_core::converter::CoreConversionContext;
+use crate::cache::{CacheInfo, CacheKey, DocumentCache};
+use crate::ast::gql_core::error::{
+    graphql_errors_to_py_err, GraphQLError, OperationError, ParseError, SchemaError,
+};
+use crate::ast::gql_core::lazy_mirror::LazyDocumentNode;
 use crate::ast::gql_core::mirror_converter::MirrorConversionContext;
-use crate::ast::gql_core::reduced_core_mirror::DocumentNode;
+use crate::ast::gql_core::reduced_core_mirror::{
+    DocumentNode, FragmentDefinitionNode, OperationDefinitionNode,
+};
+use crate::ast::operation::get_operation;
+use crate::execution::introspection::{execute_introspection, execution_result};
+use crate::execution::variables::coerce_variable_values;
+use crate::schema::diff::{diff_schemas, SchemaChange};
+use crate::schema::introspection::introspection_to_sdl;
+use crate::schema::printer::print_schema;
+use crate::schema::schema_sources;
+use crate::validation::cost::estimate_cost;
+use crate::validation::limits::ParseLimits;
+use crate::validation::python_rules::{run_python_rules, ValidationContext, VisitedNode};
+use crate::validation::{validate_document, ValidationOptions};
 
 mod ast;
+mod cache;
+mod execution;
+mod schema;
 mod util;
+mod validation;
+
+/// Source name of executable documents, unless `parse` is given another one.
+const DEFAULT_DOCUMENT_NAME: &str = "document.graphql";
 
 //use pyo3::types::{PyString,PyUnicode};
 
 #[pyclass]
 #[derive(Clone)]
 struct Document {
-    document: ExecutableDocument,
+    /// Shared with the parse cache and lazy mirror ASTs, so they need not copy it.
+    document: Arc<ExecutableDocument>,
+    /// Key of the parse cache entry this document was parsed into, if caching is enabled.
+    cache_key: Option<CacheKey>,
+}
+
+impl Document {
+    /// Selects an operation like `get_operation`, raising `OperationError` on failure.
+    fn select_operation(
+        &self,
+        py: Python<'_>,
+        operation_name: Option<&str>,
+    ) -> PyResult<&Node<Operation>> {
+        get_operation(&self.document, operation_name).map_err(|message| {
+            let error = GraphQLError::new(message, None, &self.document.sources, None);
+            graphql_errors_to_py_err::<OperationError>(
+                py,
+                "Operation could not be determined",
+                vec![error],
+            )
+        })
+    }
 }
 
 #[pymethods]
-impl Document {}
+impl Document {
+    /// Names of all operations in source order, `None` for an anonymous operation.
+    fn operation_names(&self) -> Vec<Option<&str>> {
+        self.document
+            .all_operations()
+            .map(|operation| operation.name.as_ref().map(|name| name.as_str()))
+            .collect()
+    }
 
-#[pyclass]
+    /// The operation, printed as GraphQL. Without a name, the document must contain exactly
+    /// one operation. Raises `OperationError` if the operation cannot be determined, like
+    /// `QueryCompiler.get_operation`.
+    #[pyo3(signature = (name = None))]
+    fn get_operation(&self, py: Python<'_>, name: Option<&str>) -> PyResult<String> {
+        let operation = self.select_operation(py, name)?;
+        Ok(operation.serialize().to_string())
+    }
+
+    /// `"query"`, `"mutation"` or `"subscription"`, matching graphql-core's `OperationType` values.
+    #[pyo3(signature = (name = None))]
+    fn operation_type(&self, py: Python<'_>, name: Option<&str>) -> PyResult<&'static str> {
+        let operation = self.select_operation(py, name)?;
+        Ok(match operation.operation_type {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        })
+    }
+
+    fn fragment_names(&self) -> Vec<&str> {
+        self.document
+            .fragments
+            .keys()
+            .map(|name| name.as_str())
+            .collect()
+    }
+
+    /// Maps variable names (without `$`) of the operation to their printed type, e.g. `[ID!]!`.
+    #[pyo3(signature = (name = None))]
+    fn variable_definitions<'py>(
+        &self,
+        py: Python<'py>,
+        name: Option<&str>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let operation = self.select_operation(py, name)?;
+        let variables = PyDict::new_bound(py);
+        for variable in &operation.variables {
+            variables.set_item(variable.name.as_str(), variable.ty.to_string())?;
+        }
+        Ok(variables)
+    }
+
+    /// The source text the document was parsed from.
+    #[getter]
+    fn source(&self) -> Option<&str> {
+        self.document
+            .sources
+            .values()
+            .next()
+            .map(|source_file| source_file.source_text())
+    }
+}
+
+/// Immutable after construction apart from the parse cache, so it can be shared between
+/// threads. Parsing and validation release the GIL.
+#[pyclass(frozen)]
 struct QueryCompiler {
     schema: Valid<Schema>,
     conversion_context: CoreConversionContext,
-    mirror_conversion_context: MirrorConversionContext,
+    mirror_conversion_context: Arc<MirrorConversionContext>,
+    validation_options: ValidationOptions,
+    parse_limits: ParseLimits,
+    /// Never locked while acquiring the GIL, so threads without the GIL cannot deadlock on it.
+    cache: Mutex<DocumentCache>,
+    /// Whether converted mirror ASTs are kept in the parse cache.
+    cache_ast: bool,
 }
 
-#[pymethods]
 impl QueryCompiler {
-    #[new]
-    fn new(schema: String) -> Self {
-        let apollo_schema = Schema::parse(schema.clone(), "document.graphql").unwrap();
-        let conversion_context = Python::with_gil(|py| CoreConversionContext::new(py));
-        let mirror_conversion_context = Python::with_gil(|py| MirrorConversionContext::new(py));
-
-        Self {
-            schema: Valid::assume_valid(apollo_schema),
-            conversion_context,
-            mirror_conversion_context,
+    fn cache(&self) -> MutexGuard<'_, DocumentCache> {
+        // The cache holds no invariants a panicking thread could break
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Parses the document, or returns it from the parse cache. Must be called without the GIL.
+    fn parse_cached(&self, source_name: &str, source: &str) -> Result<Document, Vec<GraphQLError>> {
+        if !self.cache().is_enabled() {
+            return self.parse_document(source_name, source).map(|document| Document {
+                document: Arc::new(document),
+                cache_key: None,
+            });
+        }
+
+        let key = CacheKey::new(source_name, source);
+        let cached_document = self
+            .cache()
+            .get(&key)
+            .map(|entry| entry.document.clone());
+        if let Some(document) = cached_document {
+            return Ok(Document {
+                document,
+                cache_key: Some(key),
+            });
+        }
+
+        let document = Arc::new(self.parse_document(source_name, source)?);
+        self.cache().insert(key.clone(), document.clone());
+        Ok(Document {
+            document,
+            cache_key: Some(key),
+        })
+    }
+
+    /// Validates the document. Results for the compiler's default options are cached
+    /// for documents parsed through the parse cache.
+    fn validate_cached(
+        &self,
+        py: Python<'_>,
+        document: Document,
+        max_depth: Option<usize>,
+        max_cost: Option<f64>,
+        rules: Option<Vec<Bound<'_, PyAny>>>,
+    ) -> PyResult<Vec<GraphQLError>> {
+        let uses_defaults = max_depth.is_none() && max_cost.is_none() && rules.is_none();
+        let cache_key = document.cache_key.filter(|_| uses_defaults);
+
+        if let Some(errors) = self.cached_validation_errors(cache_key.as_ref()) {
+            return Ok(errors);
+        }
+
+        let options = self.validation_options(max_depth, max_cost);
+        let document = Arc::unwrap_or_clone(document.document);
+        let errors = self.collect_validation_errors(py, document, &options, rules)?;
+
+        self.store_validation_errors(cache_key.as_ref(), &errors);
+        Ok(errors)
+    }
+
+    /// Parses and validates `source` with the compiler's default options, returning parse
+    /// errors if it cannot be parsed. Must be called without the GIL.
+    fn parse_and_validate(&self, source: &str) -> Vec<GraphQLError> {
+        let document = match self.parse_cached(DEFAULT_DOCUMENT_NAME, source) {
+            Ok(document) => document,
+            Err(errors) => return errors,
+        };
+        if let Some(errors) = self.cached_validation_errors(document.cache_key.as_ref()) {
+            return errors;
         }
+
+        let (_, errors) = validate_document(
+            &self.schema,
+            Arc::unwrap_or_clone(document.document),
+            &self.validation_options,
+        );
+        self.store_validation_errors(document.cache_key.as_ref(), &errors);
+        errors
+    }
+
+    fn cached_validation_errors(
+        &self,
+        cache_key: Option<&CacheKey>,
+    ) -> Option<Vec<GraphQLError>> {
+        cache_key.and_then(|key| {
+            self.cache()
+                .peek_mut(key)
+                .and_then(|entry| entry.validation_errors.clone())
+        })
     }
 
-    fn parse(&mut self, document: &str) -> PyResult<Document> {
-        let result = ExecutableDocument::parse(&self.schema, document, "document.graphql");
-
-        match result {
-            Ok(valid_doc) => Ok(Document {
-                document: valid_doc,
-            }),
-            Err(with_errors) => {
-                let errors = with_errors.errors;
-                panic!("{:?}", errors);
-                // let rustberry_errors = errors
-                //     .iter()
-                //     .map(|e| GraphQLError {
-                //         compiler_error: ApolloGraphQLError::new(
-                //             e.error.unstable_compat_message().unwrap_or(e.to_string()),
-                //             e.error.location(),
-                //             e.sources,
-                //         ),
-                //     });
-                // Err(PyErr::new::<ApolloGraphQLError, _>(format!("{:?}", rustberry_errors)))
+    fn store_validation_errors(&self, cache_key: Option<&CacheKey>, errors: &[GraphQLError]) {
+        if let Some(key) = cache_key {
+            if let Some(entry) = self.cache().peek_mut(key) {
+                entry.validation_errors = Some(errors.to_vec());
             }
         }
     }
 
-    fn add_validate(&mut self, document: &str) -> PyResult<bool> {
-        let parsed_result = ExecutableDocument::parse(&self.schema, document, "document.graphql");
+    /// Parses an executable document, enforcing the configured parse limits.
+    fn parse_document(
+        &self,
+        source_name: &str,
+        source: &str,
+    ) -> Result<ExecutableDocument, Vec<GraphQLError>> {
+        let document = self.parse_limits.parse(&self.schema, source, source_name)?;
 
-        if parsed_result.is_err() {
-            return Ok(false);
+        let errors = self.parse_limits.check(&document);
+        if errors.is_empty() {
+            Ok(document)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validates the document without the GIL, then runs the Python `rules` once the built-in
+    /// validation passed.
+    fn collect_validation_errors(
+        &self,
+        py: Python<'_>,
+        document: ExecutableDocument,
+        options: &ValidationOptions,
+        rules: Option<Vec<Bound<'_, PyAny>>>,
+    ) -> PyResult<Vec<GraphQLError>> {
+        let (valid_document, mut errors) =
+            py.allow_threads(|| validate_document(&self.schema, document, options));
+        if let (Some(valid_document), Some(rules)) = (valid_document, rules) {
+            errors.extend(run_python_rules(py, &valid_document, &rules)?);
         }
+        Ok(errors)
+    }
 
-        Ok(parsed_result.unwrap().validate(&self.schema).is_ok())
+    /// The compiler's validation options with the per-call overrides applied.
+    fn validation_options(
+        &self,
+        max_depth: Option<usize>,
+        max_cost: Option<f64>,
+    ) -> ValidationOptions {
+        let mut options = self.validation_options.clone();
+        if max_depth.is_some() {
+            options.max_depth = max_depth;
+        }
+        if max_cost.is_some() {
+            options.max_cost = max_cost;
+        }
+        options
     }
+}
 
-    fn validate(&mut self, document: Document) -> PyResult<bool> {
-        let result = document.document.validate(&self.schema);
+/// Parses the schema sources (see `schema_sources`) and validates the schema unless
+/// `assume_valid` is set, raising `SchemaError` on failure.
+fn build_schema(
+    py: Python<'_>,
+    schema: &Bound<'_, PyAny>,
+    assume_valid: bool,
+) -> PyResult<Valid<Schema>> {
+    let mut schema_builder = Schema::builder();
+    for (path, sdl) in schema_sources(schema)? {
+        schema_builder = schema_builder.parse(sdl, path);
+    }
+    let apollo_schema = schema_builder.build().map_err(|with_errors| {
+        graphql_errors_to_py_err::<SchemaError>(
+            py,
+            "Schema could not be parsed",
+            GraphQLError::from_diagnostics(&with_errors.errors),
+        )
+    })?;
 
-        match result {
-            Ok(valid_doc) => Ok(true),
-            Err(with_errors) => {
-                let errors = with_errors.errors;
-                // let rustberry_errors = errors
-                //     .iter()
-                //     .map(|e| GraphQLError {
-                //         compiler_error: ApolloGraphQLError::new(
-                //             e.error.unstable_compat_message().unwrap_or(e.to_string()),
-                //             e.error.location(),
-                //             e.sources,
-                //         ),
-                //     });
-                Ok(false)
-            }
+    let schema = if assume_valid {
+        Valid::assume_valid(apollo_schema)
+    } else {
+        apollo_schema.validate().map_err(|with_errors| {
+            graphql_errors_to_py_err::<SchemaError>(
+                py,
+                "Schema is invalid",
+                GraphQLError::from_diagnostics(&with_errors.errors),
+            )
+        })?
+    };
+    Ok(schema)
+}
+
+#[pymethods]
+impl QueryCompiler {
+    /// Parses and validates `schema`, given as SDL text, as mapping of file names to SDL text or
+    /// as list of paths of files to read. Each file is its own source, so type extensions
+    /// merge across files and diagnostics report the file they occur in.
+    /// Pass `assume_valid=True` to skip validation for schemas which have already been
+    /// validated elsewhere; syntax errors are still reported.
+    /// `max_depth` rejects documents nesting fields deeper than the given depth and `max_cost`
+    /// documents whose operations exceed the given static cost (see `estimate_cost`). Variable
+    /// values are not known yet, so lists sized by a variable take its default value, else their
+    /// `assumedSize`, and are unbounded otherwise.
+    /// With `disable_introspection`, documents selecting introspection fields other than
+    /// `__typename` are invalid. The remaining limits are enforced by `parse`, see `ParseLimits`.
+    #[new]
+    #[pyo3(signature = (
+        schema,
+        assume_valid = false,
+        max_depth = None,
+        max_cost = None,
+        disable_introspection = false,
+        max_tokens = None,
+        max_recursion = None,
+        max_aliases = None,
+        max_root_fields = None,
+        max_directives_per_field = None,
+        cache_size = 0,
+        cache_ast = false,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python<'_>,
+        schema: &Bound<'_, PyAny>,
+        assume_valid: bool,
+        max_depth: Option<usize>,
+        max_cost: Option<f64>,
+        disable_introspection: bool,
+        max_tokens: Option<usize>,
+        max_recursion: Option<usize>,
+        max_aliases: Option<usize>,
+        max_root_fields: Option<usize>,
+        max_directives_per_field: Option<usize>,
+        cache_size: usize,
+        cache_ast: bool,
+    ) -> PyResult<Self> {
+        let schema = build_schema(py, schema, assume_valid)?;
+
+        let mirror_conversion_context = Arc::new(MirrorConversionContext::new(py, &schema));
+
+        Ok(Self {
+            schema,
+            conversion_context: CoreConversionContext::new(py),
+            mirror_conversion_context,
+            validation_options: ValidationOptions {
+                max_depth,
+                max_cost,
+                disable_introspection,
+            },
+            parse_limits: ParseLimits {
+                max_tokens,
+                max_recursion,
+                max_aliases,
+                max_root_fields,
+                max_directives_per_field,
+            },
+            cache: Mutex::new(DocumentCache::new(cache_size)),
+            cache_ast,
+        })
+    }
+
+    /// Creates a compiler for the schema described by an introspection result, given as dict
+    /// or JSON text. Keyword arguments are passed on to the constructor.
+    #[classmethod]
+    #[pyo3(signature = (introspection, **kwargs))]
+    fn from_introspection<'py>(
+        cls: &Bound<'py, PyType>,
+        introspection: &Bound<'py, PyAny>,
+        kwargs: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let schema = introspection_to_sdl(cls.py(), introspection)?;
+        cls.call((schema,), kwargs)
+    }
+
+    /// Prints the schema as SDL, with type extensions merged into their types. `sorted` orders
+    /// types, fields, arguments and enum values by name for a canonical snapshot, and
+    /// `include_builtins` also prints the built-in scalars, directives and introspection types.
+    #[pyo3(signature = (sorted = false, include_builtins = false))]
+    fn print_schema(&self, sorted: bool, include_builtins: bool) -> String {
+        print_schema(&self.schema, sorted, include_builtins)
+    }
+
+    /// Compares this schema with the schema of `other`, see `diff_schemas`.
+    fn diff(&self, other: &QueryCompiler) -> Vec<SchemaChange> {
+        diff_schemas(&self.schema, &other.schema)
+    }
+
+    /// Raises `ParseError` on syntax errors and when a parse limit is exceeded.
+    /// `source_name` is the file name reported by diagnostics and locations.
+    #[pyo3(signature = (document, source_name = DEFAULT_DOCUMENT_NAME))]
+    fn parse(&self, py: Python<'_>, document: &str, source_name: &str) -> PyResult<Document> {
+        match py.allow_threads(|| self.parse_cached(source_name, document)) {
+            Ok(document) => Ok(document),
+            Err(errors) => Err(graphql_errors_to_py_err::<ParseError>(
+                py,
+                "Document could not be parsed",
+                errors,
+            )),
         }
     }
-    fn gql_core_ast(&mut self, py: Python<'_>, document: &Document) -> PyResult<PyObject> {
+
+    #[pyo3(signature = (document, max_depth = None, max_cost = None))]
+    fn add_validate(
+        &self,
+        py: Python<'_>,
+        document: &str,
+        max_depth: Option<usize>,
+        max_cost: Option<f64>,
+    ) -> PyResult<bool> {
+        let Ok(parsed_document) =
+            py.allow_threads(|| self.parse_cached(DEFAULT_DOCUMENT_NAME, document))
+        else {
+            return Ok(false);
+        };
+
+        let errors = self.validate_cached(py, parsed_document, max_depth, max_cost, None)?;
+        Ok(errors.is_empty())
+    }
+
+    /// `max_depth` and `max_cost` override the limits configured on the compiler for this call.
+    /// `rules` are Python objects defining visitor methods such as `enter_field(node, context)`,
+    /// which report errors through `context.report_error(message, node)`.
+    #[pyo3(signature = (document, max_depth = None, max_cost = None, rules = None))]
+    fn validate(
+        &self,
+        py: Python<'_>,
+        document: Document,
+        max_depth: Option<usize>,
+        max_cost: Option<f64>,
+        rules: Option<Vec<Bound<'_, PyAny>>>,
+    ) -> PyResult<bool> {
+        let errors = self.validate_cached(py, document, max_depth, max_cost, rules)?;
+        Ok(errors.is_empty())
+    }
+
+    /// Validates the document and returns every diagnostic as a `GraphQLError`.
+    /// An empty list means the document is valid.
+    #[pyo3(signature = (document, max_depth = None, max_cost = None, rules = None))]
+    fn validation_errors(
+        &self,
+        py: Python<'_>,
+        document: Document,
+        max_depth: Option<usize>,
+        max_cost: Option<f64>,
+        rules: Option<Vec<Bound<'_, PyAny>>>,
+    ) -> PyResult<Vec<GraphQLError>> {
+        self.validate_cached(py, document, max_depth, max_cost, rules)
+    }
+
+    /// Parses and validates every source in parallel with the compiler's default options.
+    /// Returns a list of `GraphQLError`s per source, in order; an empty list means the
+    /// document is valid. Parse errors are reported like validation errors.
+    fn validate_many(&self, py: Python<'_>, sources: Vec<String>) -> Vec<Vec<GraphQLError>> {
+        py.allow_threads(|| {
+            sources
+                .par_iter()
+                .map(|source| self.parse_and_validate(source))
+                .collect()
+        })
+    }
+
+    fn gql_core_ast(&self, py: Python<'_>, document: &Document) -> PyResult<PyObject> {
         // let ast = self.compiler.db.ast(file_id.file_id);
         let gql_core_ast = self
             .conversion_context
@@ -107,22 +510,202 @@ impl QueryCompiler {
         Ok(gql_core_ast?)
     }
 
-    fn gql_core_ast_mirror(&mut self, py: Python<'_>, document: &Document) -> PyResult<Py<DocumentNode>> {
-        // let ast = self.compiler.db.ast(file_id.file_id);
+    /// With `lazy`, nodes are only converted once their attributes are read and the result is
+    /// not kept in the parse cache, as creating it is cheap.
+    #[pyo3(signature = (document, no_location = false, lazy = false))]
+    fn gql_core_ast_mirror(
+        &self,
+        py: Python<'_>,
+        document: &Document,
+        no_location: bool,
+        lazy: bool,
+    ) -> PyResult<PyObject> {
+        if lazy {
+            let lazy_ast = LazyDocumentNode::new(
+                self.mirror_conversion_context.clone(),
+                document.document.clone(),
+                no_location,
+            );
+            return Ok(Py::new(py, lazy_ast)?.into_py(py));
+        }
+
+        let cache_key = document
+            .cache_key
+            .as_ref()
+            .filter(|_| self.cache_ast && !no_location);
+        let cached_ast = cache_key.and_then(|key| {
+            self.cache()
+                .peek_mut(key)
+                .and_then(|entry| entry.mirror_ast.as_ref().map(|ast| ast.clone_ref(py)))
+        });
+        if let Some(ast) = cached_ast {
+            return Ok(ast.into_py(py));
+        }
+
         let gql_core_ast = self
             .mirror_conversion_context
-            .convert_core_to_core_ast(py, &document.document);
+            .convert_core_to_core_ast(py, &document.document, no_location);
+        let gql_core_ast = Py::new(py, gql_core_ast)?;
 
-        Py::new(py, gql_core_ast)
+        if let Some(key) = cache_key {
+            if let Some(entry) = self.cache().peek_mut(key) {
+                entry.mirror_ast = Some(gql_core_ast.clone_ref(py));
+            }
+        }
+        Ok(gql_core_ast.into_py(py))
     }
+
+    fn cache_info(&self) -> CacheInfo {
+        self.cache().info()
+    }
+
+    fn cache_clear(&self) {
+        self.cache().clear();
+    }
+
+    /// Selects the operation to execute as described by GetOperation in the spec and returns it
+    /// as mirror node along with the fragment definitions it uses.
+    /// Raises `OperationError` if the name is unknown or required but missing.
+    #[pyo3(signature = (document, operation_name = None, no_location = false))]
+    fn get_operation(
+        &self,
+        py: Python<'_>,
+        document: &Document,
+        operation_name: Option<&str>,
+        no_location: bool,
+    ) -> PyResult<(OperationDefinitionNode, Vec<FragmentDefinitionNode>)> {
+        let operation = document.select_operation(py, operation_name)?;
+
+        Ok(self.mirror_conversion_context.convert_operation_with_fragments(
+            py,
+            &document.document,
+            operation,
+            no_location,
+        ))
+    }
+
+    /// Coerces raw variable values against the variable definitions of the selected operation.
+    /// Returns the coerced values as dict, or a list of `GraphQLError`s like graphql-core's
+    /// `get_variable_values`.
+    #[pyo3(signature = (document, operation_name, variables))]
+    fn coerce_variables(
+        &self,
+        py: Python<'_>,
+        document: &Document,
+        operation_name: Option<&str>,
+        variables: &Bound<'_, PyDict>,
+    ) -> PyResult<PyObject> {
+        let operation = document.select_operation(py, operation_name)?;
+
+        let coerced = coerce_variable_values(py, &self.schema, &document.document, operation, variables)?;
+        Ok(match coerced {
+            Ok(values) => values.into_py(py),
+            Err(errors) => errors.into_py(py),
+        })
+    }
+
+    /// Executes the introspection meta-fields `__schema`, `__type` and `__typename` of the
+    /// selected operation from the schema, without any Python resolvers. Returns a dict like
+    /// graphql-core's `ExecutionResult.formatted`, holding validation and variable coercion
+    /// errors if the document cannot be executed.
+    #[pyo3(signature = (document, operation_name = None, variables = None))]
+    fn execute_introspection<'py>(
+        &self,
+        py: Python<'py>,
+        document: &Document,
+        operation_name: Option<&str>,
+        variables: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let (_, errors) = py.allow_threads(|| {
+            let document = ExecutableDocument::clone(&document.document);
+            validate_document(&self.schema, document, &self.validation_options)
+        });
+        if !errors.is_empty() {
+            return execution_result(py, None, &errors);
+        }
+
+        let operation = document.select_operation(py, operation_name)?;
+        let variables = match variables {
+            Some(variables) => variables.clone(),
+            None => PyDict::new_bound(py),
+        };
+        let coerced =
+            coerce_variable_values(py, &self.schema, &document.document, operation, &variables)?;
+        match coerced {
+            Ok(coerced) => {
+                execute_introspection(py, &self.schema, &document.document, operation, &coerced)
+            }
+            Err(errors) => execution_result(py, None, &errors),
+        }
+    }
+
+    /// Estimates the static cost of the selected operation, returning a dict with the `total`
+    /// and the cost of each field by response path under `fields`.
+    /// Numeric `variables` are used to resolve list sizes.
+    #[pyo3(signature = (document, variables = None, operation_name = None))]
+    fn estimate_cost<'py>(
+        &self,
+        py: Python<'py>,
+        document: &Document,
+        variables: Option<&Bound<'py, PyDict>>,
+        operation_name: Option<&str>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let operation = document.select_operation(py, operation_name)?;
+
+        let mut numeric_variables = HashMap::new();
+        for (name, value) in variables.into_iter().flat_map(|variables| variables.iter()) {
+            if value.is_instance_of::<PyBool>() {
+                continue;
+            }
+            if let Ok(number) = value.extract::<f64>() {
+                numeric_variables.insert(name.extract::<String>()?, number);
+            }
+        }
+
+        let estimate =
+            estimate_cost(&self.schema, &document.document, operation, &numeric_variables);
+
+        let fields = PyDict::new_bound(py);
+        for (path, cost) in estimate.fields {
+            fields.set_item(path, cost)?;
+        }
+        let result = PyDict::new_bound(py);
+        result.set_item("total", estimate.total)?;
+        result.set_item("fields", fields)?;
+        Ok(result)
+    }
+}
+
+/// Compares two schemas, given in any form `QueryCompiler` accepts, and lists the changes
+/// classified as breaking, dangerous or safe like graphql-core's `find_breaking_changes`.
+#[pyfunction]
+#[pyo3(name = "diff_schemas")]
+fn py_diff_schemas(
+    py: Python<'_>,
+    old_schema: &Bound<'_, PyAny>,
+    new_schema: &Bound<'_, PyAny>,
+) -> PyResult<Vec<SchemaChange>> {
+    let old_schema = build_schema(py, old_schema, false)?;
+    let new_schema = build_schema(py, new_schema, false)?;
+    Ok(diff_schemas(&old_schema, &new_schema))
 }
 
 /// A Python module implemented in Rust. The name of this function must match
 /// the `lib.name` setting in the `Cargo.toml`, else Python will not be able to
 /// import the module.
 #[pymodule]
-fn _rustberry(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+fn _rustberry(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<QueryCompiler>()?;
+    m.add_class::<Document>()?;
+    m.add_class::<CacheInfo>()?;
+    m.add_class::<ValidationContext>()?;
+    m.add_class::<VisitedNode>()?;
+    m.add_class::<GraphQLError>()?;
+    m.add_class::<SchemaChange>()?;
+    m.add_function(wrap_pyfunction!(py_diff_schemas, m)?)?;
+    m.add("ParseError", py.get_type_bound::<ParseError>())?;
+    m.add("SchemaError", py.get_type_bound::<SchemaError>())?;
+    m.add("OperationError", py.get_type_bound::<OperationError>())?;
 
     Ok(())
 }
@@ -0,0 +1,605 @@
+use apollo_compiler::schema::{
+    DirectiveDefinition, ExtendedType, FieldDefinition, InputValueDefinition, Type, Value,
+};
+use apollo_compiler::{Node, Schema};
+use pyo3::prelude::*;
+
+use crate::schema::printer::is_built_in_type;
+
+#[derive(Clone, Copy)]
+pub enum Criticality {
+    Breaking,
+    Dangerous,
+    Safe,
+}
+
+impl Criticality {
+    fn as_str(self) -> &'static str {
+        match self {
+            Criticality::Breaking => "BREAKING",
+            Criticality::Dangerous => "DANGEROUS",
+            Criticality::Safe => "SAFE",
+        }
+    }
+}
+
+/// A difference between two schemas. `type` is the name of graphql-core's
+/// `BreakingChangeType` or `DangerousChangeType` member where one exists.
+#[pyclass(frozen)]
+pub struct SchemaChange {
+    criticality: Criticality,
+    #[pyo3(get)]
+    r#type: String,
+    #[pyo3(get)]
+    description: String,
+}
+
+#[pymethods]
+impl SchemaChange {
+    /// `"BREAKING"`, `"DANGEROUS"` or `"SAFE"`.
+    #[getter]
+    fn criticality(&self) -> &'static str {
+        self.criticality.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SchemaChange(criticality={:?}, type={:?}, description={:?})",
+            self.criticality.as_str(),
+            self.r#type,
+            self.description
+        )
+    }
+}
+
+fn kind_name(ty: &ExtendedType) -> &'static str {
+    match ty {
+        ExtendedType::Scalar(_) => "a Scalar type",
+        ExtendedType::Object(_) => "an Object type",
+        ExtendedType::Interface(_) => "an Interface type",
+        ExtendedType::Union(_) => "a Union type",
+        ExtendedType::Enum(_) => "an Enum type",
+        ExtendedType::InputObject(_) => "an Input type",
+    }
+}
+
+fn nullable(ty: &Type) -> Type {
+    match ty {
+        Type::NonNullNamed(name) => Type::Named(name.clone()),
+        Type::NonNullList(item_type) => Type::List(item_type.clone()),
+        _ => ty.clone(),
+    }
+}
+
+/// Clients can still handle the new type of an output field: it may only become non-null.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match old {
+        Type::Named(old_name) => {
+            matches!(new, Type::Named(new_name) | Type::NonNullNamed(new_name) if new_name == old_name)
+        }
+        Type::List(old_item) => match new {
+            Type::List(new_item) | Type::NonNullList(new_item) => {
+                is_safe_output_change(old_item, new_item)
+            }
+            _ => false,
+        },
+        Type::NonNullNamed(_) | Type::NonNullList(_) => {
+            new.is_non_null() && is_safe_output_change(&nullable(old), &nullable(new))
+        }
+    }
+}
+
+/// Values clients send are still accepted by the new type of an input: it may only become
+/// nullable.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    match old {
+        Type::Named(old_name) => matches!(new, Type::Named(new_name) if new_name == old_name),
+        Type::List(old_item) => {
+            matches!(new, Type::List(new_item) if is_safe_input_change(old_item, new_item))
+        }
+        Type::NonNullNamed(_) | Type::NonNullList(_) => {
+            is_safe_input_change(&nullable(old), &nullable(new))
+        }
+    }
+}
+
+/// The value with object fields sorted by name like graphql-core's `sort_value_node`, so
+/// default values only differing in field order compare equal.
+fn sort_value(value: &Value) -> Value {
+    match value {
+        Value::List(items) => {
+            Value::List(items.iter().map(|item| Node::new(sort_value(item))).collect())
+        }
+        Value::Object(fields) => {
+            let mut fields: Vec<_> = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), Node::new(sort_value(value))))
+                .collect();
+            fields.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+            Value::Object(fields)
+        }
+        _ => value.clone(),
+    }
+}
+
+fn is_required(input_value: &InputValueDefinition) -> bool {
+    input_value.ty.is_non_null() && input_value.default_value.is_none()
+}
+
+#[derive(Default)]
+struct Changes {
+    changes: Vec<SchemaChange>,
+}
+
+impl Changes {
+    fn push(&mut self, criticality: Criticality, change_type: &'static str, description: String) {
+        self.changes.push(SchemaChange {
+            criticality,
+            r#type: change_type.to_string(),
+            description,
+        });
+    }
+
+    fn diff_types(&mut self, old_schema: &Schema, new_schema: &Schema) {
+        let old_types = old_schema
+            .types
+            .iter()
+            .filter(|(name, _)| !is_built_in_type(name.as_str()));
+        let new_types = new_schema
+            .types
+            .iter()
+            .filter(|(name, _)| !is_built_in_type(name.as_str()));
+
+        for (name, _) in old_types.clone() {
+            if !new_schema.types.contains_key(name) {
+                self.push(
+                    Criticality::Breaking,
+                    "TYPE_REMOVED",
+                    format!("{name} was removed."),
+                );
+            }
+        }
+        for (name, _) in new_types {
+            if !old_schema.types.contains_key(name) {
+                self.push(
+                    Criticality::Safe,
+                    "TYPE_ADDED",
+                    format!("{name} was added."),
+                );
+            }
+        }
+
+        for (name, old_type) in old_types {
+            let Some(new_type) = new_schema.types.get(name) else {
+                continue;
+            };
+            match (old_type, new_type) {
+                (ExtendedType::Enum(old_enum), ExtendedType::Enum(new_enum)) => {
+                    for value in new_enum.values.keys() {
+                        if !old_enum.values.contains_key(value) {
+                            self.push(
+                                Criticality::Dangerous,
+                                "VALUE_ADDED_TO_ENUM",
+                                format!("{value} was added to enum type {name}."),
+                            );
+                        }
+                    }
+                    for value in old_enum.values.keys() {
+                        if !new_enum.values.contains_key(value) {
+                            self.push(
+                                Criticality::Breaking,
+                                "VALUE_REMOVED_FROM_ENUM",
+                                format!("{value} was removed from enum type {name}."),
+                            );
+                        }
+                    }
+                }
+                (ExtendedType::Union(old_union), ExtendedType::Union(new_union)) => {
+                    for member in &new_union.members {
+                        if !old_union.members.iter().any(|old| old.name == member.name) {
+                            self.push(
+                                Criticality::Dangerous,
+                                "TYPE_ADDED_TO_UNION",
+                                format!("{} was added to union type {name}.", member.name),
+                            );
+                        }
+                    }
+                    for member in &old_union.members {
+                        if !new_union.members.iter().any(|new| new.name == member.name) {
+                            self.push(
+                                Criticality::Breaking,
+                                "TYPE_REMOVED_FROM_UNION",
+                                format!("{} was removed from union type {name}.", member.name),
+                            );
+                        }
+                    }
+                }
+                (ExtendedType::InputObject(old_input), ExtendedType::InputObject(new_input)) => {
+                    for (field_name, new_field) in &new_input.fields {
+                        if old_input.fields.contains_key(field_name) {
+                            continue;
+                        }
+                        if is_required(new_field) {
+                            self.push(
+                                Criticality::Breaking,
+                                "REQUIRED_INPUT_FIELD_ADDED",
+                                format!(
+                                    "A required field {field_name} on input type {name} was added."
+                                ),
+                            );
+                        } else {
+                            self.push(
+                                Criticality::Dangerous,
+                                "OPTIONAL_INPUT_FIELD_ADDED",
+                                format!("An optional field {field_name} on input type {name} was added."),
+                            );
+                        }
+                    }
+                    for (field_name, old_field) in &old_input.fields {
+                        let Some(new_field) = new_input.fields.get(field_name) else {
+                            self.push(
+                                Criticality::Breaking,
+                                "FIELD_REMOVED",
+                                format!("{name}.{field_name} was removed."),
+                            );
+                            continue;
+                        };
+                        if old_field.ty == new_field.ty {
+                            continue;
+                        }
+                        let description = format!(
+                            "{name}.{field_name} changed type from {} to {}.",
+                            old_field.ty, new_field.ty
+                        );
+                        if is_safe_input_change(&old_field.ty, &new_field.ty) {
+                            self.push(Criticality::Safe, "FIELD_TYPE_CHANGED", description);
+                        } else {
+                            self.push(Criticality::Breaking, "FIELD_CHANGED_KIND", description);
+                        }
+                    }
+                }
+                (ExtendedType::Object(old_object), ExtendedType::Object(new_object)) => {
+                    self.diff_interfaces(
+                        name,
+                        &old_object
+                            .implements_interfaces
+                            .iter()
+                            .map(|name| &name.name)
+                            .collect::<Vec<_>>(),
+                        &new_object
+                            .implements_interfaces
+                            .iter()
+                            .map(|name| &name.name)
+                            .collect::<Vec<_>>(),
+                    );
+                    self.diff_fields(
+                        name,
+                        old_object
+                            .fields
+                            .iter()
+                            .map(|(name, field)| (name, &**field))
+                            .collect(),
+                        new_object
+                            .fields
+                            .iter()
+                            .map(|(name, field)| (name, &**field))
+                            .collect(),
+                    );
+                }
+                (
+                    ExtendedType::Interface(old_interface),
+                    ExtendedType::Interface(new_interface),
+                ) => {
+                    self.diff_interfaces(
+                        name,
+                        &old_interface
+                            .implements_interfaces
+                            .iter()
+                            .map(|name| &name.name)
+                            .collect::<Vec<_>>(),
+                        &new_interface
+                            .implements_interfaces
+                            .iter()
+                            .map(|name| &name.name)
+                            .collect::<Vec<_>>(),
+                    );
+                    self.diff_fields(
+                        name,
+                        old_interface
+                            .fields
+                            .iter()
+                            .map(|(name, field)| (name, &**field))
+                            .collect(),
+                        new_interface
+                            .fields
+                            .iter()
+                            .map(|(name, field)| (name, &**field))
+                            .collect(),
+                    );
+                }
+                (ExtendedType::Scalar(_), ExtendedType::Scalar(_)) => {}
+                _ => self.push(
+                    Criticality::Breaking,
+                    "TYPE_CHANGED_KIND",
+                    format!(
+                        "{name} changed from {} to {}.",
+                        kind_name(old_type),
+                        kind_name(new_type)
+                    ),
+                ),
+            }
+        }
+    }
+
+    fn diff_interfaces<N: PartialEq + std::fmt::Display>(
+        &mut self,
+        type_name: &str,
+        old_interfaces: &[N],
+        new_interfaces: &[N],
+    ) {
+        for interface in new_interfaces {
+            if !old_interfaces.contains(interface) {
+                self.push(
+                    Criticality::Dangerous,
+                    "IMPLEMENTED_INTERFACE_ADDED",
+                    format!("{interface} added to interfaces implemented by {type_name}."),
+                );
+            }
+        }
+        for interface in old_interfaces {
+            if !new_interfaces.contains(interface) {
+                self.push(
+                    Criticality::Breaking,
+                    "IMPLEMENTED_INTERFACE_REMOVED",
+                    format!("{type_name} no longer implements interface {interface}."),
+                );
+            }
+        }
+    }
+
+    fn diff_fields<N: PartialEq + std::fmt::Display>(
+        &mut self,
+        type_name: &str,
+        old_fields: Vec<(N, &FieldDefinition)>,
+        new_fields: Vec<(N, &FieldDefinition)>,
+    ) {
+        for (field_name, _) in &new_fields {
+            if !old_fields
+                .iter()
+                .any(|(old_name, _)| old_name == field_name)
+            {
+                self.push(
+                    Criticality::Safe,
+                    "FIELD_ADDED",
+                    format!("{type_name}.{field_name} was added."),
+                );
+            }
+        }
+        for (field_name, old_field) in &old_fields {
+            let Some((_, new_field)) = new_fields
+                .iter()
+                .find(|(new_name, _)| new_name == field_name)
+            else {
+                self.push(
+                    Criticality::Breaking,
+                    "FIELD_REMOVED",
+                    format!("{type_name}.{field_name} was removed."),
+                );
+                continue;
+            };
+
+            self.diff_arguments(
+                &format!("{type_name}.{field_name}"),
+                &old_field.arguments,
+                &new_field.arguments,
+            );
+
+            if old_field.ty == new_field.ty {
+                continue;
+            }
+            let description = format!(
+                "{type_name}.{field_name} changed type from {} to {}.",
+                old_field.ty, new_field.ty
+            );
+            if is_safe_output_change(&old_field.ty, &new_field.ty) {
+                self.push(Criticality::Safe, "FIELD_TYPE_CHANGED", description);
+            } else {
+                self.push(Criticality::Breaking, "FIELD_CHANGED_KIND", description);
+            }
+        }
+    }
+
+    fn diff_arguments(
+        &mut self,
+        field_path: &str,
+        old_arguments: &[Node<InputValueDefinition>],
+        new_arguments: &[Node<InputValueDefinition>],
+    ) {
+        for old_argument in old_arguments {
+            let argument_name = &old_argument.name;
+            let Some(new_argument) = new_arguments.iter().find(|new| new.name == *argument_name)
+            else {
+                self.push(
+                    Criticality::Breaking,
+                    "ARG_REMOVED",
+                    format!("{field_path} arg {argument_name} was removed."),
+                );
+                continue;
+            };
+
+            if !is_safe_input_change(&old_argument.ty, &new_argument.ty) {
+                self.push(
+                    Criticality::Breaking,
+                    "ARG_CHANGED_KIND",
+                    format!(
+                        "{field_path} arg {argument_name} has changed type from {} to {}.",
+                        old_argument.ty, new_argument.ty
+                    ),
+                );
+                continue;
+            }
+            if old_argument.ty != new_argument.ty {
+                self.push(
+                    Criticality::Safe,
+                    "ARG_TYPE_CHANGED",
+                    format!(
+                        "{field_path} arg {argument_name} has changed type from {} to {}.",
+                        old_argument.ty, new_argument.ty
+                    ),
+                );
+            }
+
+            let Some(old_default) = &old_argument.default_value else {
+                continue;
+            };
+            match &new_argument.default_value {
+                None => self.push(
+                    Criticality::Dangerous,
+                    "ARG_DEFAULT_VALUE_CHANGE",
+                    format!("{field_path} arg {argument_name} defaultValue was removed."),
+                ),
+                Some(new_default) => {
+                    let (old_default, new_default) = (
+                        sort_value(old_default).to_string(),
+                        sort_value(new_default).to_string(),
+                    );
+                    if old_default != new_default {
+                        self.push(
+                            Criticality::Dangerous,
+                            "ARG_DEFAULT_VALUE_CHANGE",
+                            format!(
+                                "{field_path} arg {argument_name} has changed defaultValue from {old_default} to {new_default}."
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        for new_argument in new_arguments {
+            let argument_name = &new_argument.name;
+            if old_arguments.iter().any(|old| old.name == *argument_name) {
+                continue;
+            }
+            if is_required(new_argument) {
+                self.push(
+                    Criticality::Breaking,
+                    "REQUIRED_ARG_ADDED",
+                    format!("A required arg {argument_name} on {field_path} was added."),
+                );
+            } else {
+                self.push(
+                    Criticality::Dangerous,
+                    "OPTIONAL_ARG_ADDED",
+                    format!("An optional arg {argument_name} on {field_path} was added."),
+                );
+            }
+        }
+    }
+
+    fn diff_directives(&mut self, old_schema: &Schema, new_schema: &Schema) {
+        for (name, old_directive) in &old_schema.directive_definitions {
+            let Some(new_directive) = new_schema.directive_definitions.get(name) else {
+                self.push(
+                    Criticality::Breaking,
+                    "DIRECTIVE_REMOVED",
+                    format!("{name} was removed."),
+                );
+                continue;
+            };
+            self.diff_directive(old_directive, new_directive);
+        }
+        for name in new_schema.directive_definitions.keys() {
+            if !old_schema.directive_definitions.contains_key(name) {
+                self.push(
+                    Criticality::Safe,
+                    "DIRECTIVE_ADDED",
+                    format!("{name} was added."),
+                );
+            }
+        }
+    }
+
+    fn diff_directive(
+        &mut self,
+        old_directive: &DirectiveDefinition,
+        new_directive: &DirectiveDefinition,
+    ) {
+        let name = &old_directive.name;
+
+        for new_argument in &new_directive.arguments {
+            let argument_name = &new_argument.name;
+            if old_directive
+                .arguments
+                .iter()
+                .any(|old| old.name == *argument_name)
+            {
+                continue;
+            }
+            if is_required(new_argument) {
+                self.push(
+                    Criticality::Breaking,
+                    "REQUIRED_DIRECTIVE_ARG_ADDED",
+                    format!("A required arg {argument_name} on directive {name} was added."),
+                );
+            } else {
+                self.push(
+                    Criticality::Safe,
+                    "OPTIONAL_DIRECTIVE_ARG_ADDED",
+                    format!("An optional arg {argument_name} on directive {name} was added."),
+                );
+            }
+        }
+        for old_argument in &old_directive.arguments {
+            let argument_name = &old_argument.name;
+            if !new_directive
+                .arguments
+                .iter()
+                .any(|new| new.name == *argument_name)
+            {
+                self.push(
+                    Criticality::Breaking,
+                    "DIRECTIVE_ARG_REMOVED",
+                    format!("{argument_name} was removed from {name}."),
+                );
+            }
+        }
+
+        if old_directive.repeatable && !new_directive.repeatable {
+            self.push(
+                Criticality::Breaking,
+                "DIRECTIVE_REPEATABLE_REMOVED",
+                format!("Repeatable flag was removed from {name}."),
+            );
+        }
+
+        for location in &old_directive.locations {
+            if !new_directive.locations.contains(location) {
+                self.push(
+                    Criticality::Breaking,
+                    "DIRECTIVE_LOCATION_REMOVED",
+                    format!("{location} was removed from {name}."),
+                );
+            }
+        }
+        for location in &new_directive.locations {
+            if !old_directive.locations.contains(location) {
+                self.push(
+                    Criticality::Safe,
+                    "DIRECTIVE_LOCATION_ADDED",
+                    format!("{location} was added to {name}."),
+                );
+            }
+        }
+    }
+}
+
+/// Compares two schemas like graphql-core's `find_breaking_changes` and
+/// `find_dangerous_changes`, additionally reporting safe changes such as added types and fields.
+/// Built-in scalars and introspection types are not compared.
+pub fn diff_schemas(old_schema: &Schema, new_schema: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Changes::default();
+    changes.diff_types(old_schema, new_schema);
+    changes.diff_directives(old_schema, new_schema);
+    changes.changes
+}
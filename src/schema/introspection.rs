@@ -0,0 +1,370 @@
+use std::fmt::Write;
+use std::iter::Peekable;
+
+use apollo_parser::{Lexer, TokenKind};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+
+use crate::schema::{BUILT_IN_DIRECTIVES, BUILT_IN_SCALARS};
+
+fn invalid(message: String) -> PyErr {
+    PyTypeError::new_err(format!("Invalid or incomplete introspection result. {message}"))
+}
+
+fn get<'py>(object: &Bound<'py, PyAny>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+    let object = object
+        .downcast::<PyDict>()
+        .map_err(|_| invalid(format!("Expected an object holding '{key}'.")))?;
+    match object.get_item(key)? {
+        Some(value) if !value.is_none() => Ok(value),
+        _ => Err(invalid(format!("Missing '{key}' in: {}.", object.repr()?))),
+    }
+}
+
+fn get_str(object: &Bound<'_, PyAny>, key: &str) -> PyResult<String> {
+    get(object, key)?.extract()
+}
+
+fn get_optional<'py>(object: &Bound<'py, PyAny>, key: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let Ok(object) = object.downcast::<PyDict>() else {
+        return Ok(None);
+    };
+    Ok(object.get_item(key)?.filter(|value| !value.is_none()))
+}
+
+fn get_optional_str(object: &Bound<'_, PyAny>, key: &str) -> PyResult<Option<String>> {
+    get_optional(object, key)?.map(|value| value.extract()).transpose()
+}
+
+/// Items of a list, treating a missing list like an empty one.
+fn get_list<'py>(object: &Bound<'py, PyAny>, key: &str) -> PyResult<Vec<Bound<'py, PyAny>>> {
+    match get_optional(object, key)? {
+        Some(list) => list.iter()?.collect(),
+        None => Ok(vec![]),
+    }
+}
+
+/// Whether `name` matches `/[_A-Za-z][_0-9A-Za-z]*/`.
+fn is_name(name: &str) -> bool {
+    let mut characters = name.chars();
+    characters
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && characters.all(|character| character == '_' || character.is_ascii_alphanumeric())
+}
+
+/// A name, checked so it cannot inject anything into the SDL it is printed into.
+fn get_name(object: &Bound<'_, PyAny>, key: &str) -> PyResult<String> {
+    let name = get_str(object, key)?;
+    if is_name(&name) {
+        Ok(name)
+    } else {
+        Err(invalid(format!(
+            "Names must match /[_A-Za-z][_0-9A-Za-z]*/ but '{name}' does not."
+        )))
+    }
+}
+
+/// Nesting of lists and objects up to which default values are accepted.
+const MAX_VALUE_DEPTH: usize = 64;
+
+/// Whether `value` is exactly one constant value, so it can be printed into SDL as it is.
+fn is_const_value(value: &str) -> bool {
+    let mut tokens = Vec::new();
+    for token in Lexer::new(value) {
+        let Ok(token) = token else {
+            return false;
+        };
+        match token.kind() {
+            TokenKind::Whitespace | TokenKind::Comma => {}
+            // Would comment out the rest of the line the value is printed into
+            TokenKind::Comment => return false,
+            TokenKind::Eof => break,
+            kind => tokens.push(kind),
+        }
+    }
+    let mut tokens = tokens.into_iter().peekable();
+    is_const_value_tokens(&mut tokens, 0) && tokens.next().is_none()
+}
+
+fn is_const_value_tokens(
+    tokens: &mut Peekable<impl Iterator<Item = TokenKind>>,
+    depth: usize,
+) -> bool {
+    if depth > MAX_VALUE_DEPTH {
+        return false;
+    }
+    match tokens.next() {
+        Some(TokenKind::Int | TokenKind::Float | TokenKind::StringValue | TokenKind::Name) => true,
+        Some(TokenKind::LBracket) => loop {
+            if matches!(tokens.peek(), Some(TokenKind::RBracket)) {
+                tokens.next();
+                return true;
+            }
+            if !is_const_value_tokens(tokens, depth + 1) {
+                return false;
+            }
+        },
+        Some(TokenKind::LCurly) => loop {
+            if matches!(tokens.peek(), Some(TokenKind::RCurly)) {
+                tokens.next();
+                return true;
+            }
+            if !matches!(tokens.next(), Some(TokenKind::Name))
+                || !matches!(tokens.next(), Some(TokenKind::Colon))
+                || !is_const_value_tokens(tokens, depth + 1)
+            {
+                return false;
+            }
+        },
+        _ => false,
+    }
+}
+
+/// A GraphQL string literal.
+fn string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '\u{8}' => literal.push_str("\\b"),
+            '\u{c}' => literal.push_str("\\f"),
+            character if character.is_control() => {
+                let _ = write!(literal, "\\u{:04X}", character as u32);
+            }
+            character => literal.push(character),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn write_description(sdl: &mut String, indent: &str, object: &Bound<'_, PyAny>) -> PyResult<()> {
+    if let Some(description) = get_optional_str(object, "description")? {
+        let _ = writeln!(sdl, "{indent}{}", string_literal(&description));
+    }
+    Ok(())
+}
+
+fn write_deprecation(sdl: &mut String, object: &Bound<'_, PyAny>) -> PyResult<()> {
+    let is_deprecated = get_optional(object, "isDeprecated")?
+        .map(|value| value.extract::<bool>())
+        .transpose()?
+        .unwrap_or(false);
+    if is_deprecated {
+        match get_optional_str(object, "deprecationReason")? {
+            Some(reason) => {
+                let _ = write!(sdl, " @deprecated(reason: {})", string_literal(&reason));
+            }
+            None => sdl.push_str(" @deprecated"),
+        }
+    }
+    Ok(())
+}
+
+/// A type reference like `[String!]!`.
+fn type_reference(type_ref: &Bound<'_, PyAny>) -> PyResult<String> {
+    let kind = get_str(type_ref, "kind")?;
+    match kind.as_str() {
+        "LIST" => Ok(format!("[{}]", type_reference(&get(type_ref, "ofType")?)?)),
+        "NON_NULL" => Ok(format!("{}!", type_reference(&get(type_ref, "ofType")?)?)),
+        _ => get_name(type_ref, "name"),
+    }
+}
+
+fn write_input_value(sdl: &mut String, input_value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let name = get_name(input_value, "name")?;
+    let _ = write!(sdl, "{name}: {}", type_reference(&get(input_value, "type")?)?);
+    if let Some(default_value) = get_optional_str(input_value, "defaultValue")? {
+        if !is_const_value(&default_value) {
+            return Err(invalid(format!(
+                "Invalid default value '{default_value}' of '{name}'."
+            )));
+        }
+        let _ = write!(sdl, " = {default_value}");
+    }
+    write_deprecation(sdl, input_value)
+}
+
+fn write_arguments(sdl: &mut String, object: &Bound<'_, PyAny>) -> PyResult<()> {
+    let arguments = get_list(object, "args")?;
+    if arguments.is_empty() {
+        return Ok(());
+    }
+    sdl.push('(');
+    for (index, argument) in arguments.iter().enumerate() {
+        if index > 0 {
+            sdl.push_str(", ");
+        }
+        if let Some(description) = get_optional_str(argument, "description")? {
+            let _ = write!(sdl, "{} ", string_literal(&description));
+        }
+        write_input_value(sdl, argument)?;
+    }
+    sdl.push(')');
+    Ok(())
+}
+
+fn write_implements(sdl: &mut String, type_definition: &Bound<'_, PyAny>) -> PyResult<()> {
+    let interfaces = get_list(type_definition, "interfaces")?
+        .iter()
+        .map(|interface| get_name(interface, "name"))
+        .collect::<PyResult<Vec<_>>>()?;
+    if !interfaces.is_empty() {
+        let _ = write!(sdl, " implements {}", interfaces.join(" & "));
+    }
+    Ok(())
+}
+
+fn write_type(sdl: &mut String, type_definition: &Bound<'_, PyAny>) -> PyResult<()> {
+    let kind = get_str(type_definition, "kind")?;
+    let name = get_name(type_definition, "name")?;
+
+    write_description(sdl, "", type_definition)?;
+    match kind.as_str() {
+        "SCALAR" => {
+            let _ = write!(sdl, "scalar {name}");
+            if let Some(url) = get_optional_str(type_definition, "specifiedByURL")? {
+                let _ = write!(sdl, " @specifiedBy(url: {})", string_literal(&url));
+            }
+            sdl.push('\n');
+        }
+        "OBJECT" | "INTERFACE" => {
+            let keyword = if kind == "OBJECT" { "type" } else { "interface" };
+            let _ = write!(sdl, "{keyword} {name}");
+            write_implements(sdl, type_definition)?;
+            sdl.push_str(" {\n");
+            for field in get_list(type_definition, "fields")? {
+                write_description(sdl, "  ", &field)?;
+                let _ = write!(sdl, "  {}", get_name(&field, "name")?);
+                write_arguments(sdl, &field)?;
+                let _ = write!(sdl, ": {}", type_reference(&get(&field, "type")?)?);
+                write_deprecation(sdl, &field)?;
+                sdl.push('\n');
+            }
+            sdl.push_str("}\n");
+        }
+        "UNION" => {
+            let members = get_list(type_definition, "possibleTypes")?
+                .iter()
+                .map(|member| get_name(member, "name"))
+                .collect::<PyResult<Vec<_>>>()?;
+            let _ = writeln!(sdl, "union {name} = {}", members.join(" | "));
+        }
+        "ENUM" => {
+            let _ = writeln!(sdl, "enum {name} {{");
+            for value in get_list(type_definition, "enumValues")? {
+                write_description(sdl, "  ", &value)?;
+                let _ = write!(sdl, "  {}", get_name(&value, "name")?);
+                write_deprecation(sdl, &value)?;
+                sdl.push('\n');
+            }
+            sdl.push_str("}\n");
+        }
+        "INPUT_OBJECT" => {
+            let _ = write!(sdl, "input {name}");
+            let is_one_of = get_optional(type_definition, "isOneOf")?
+                .map(|value| value.extract::<bool>())
+                .transpose()?
+                .unwrap_or(false);
+            if is_one_of {
+                sdl.push_str(" @oneOf");
+            }
+            sdl.push_str(" {\n");
+            for input_field in get_list(type_definition, "inputFields")? {
+                write_description(sdl, "  ", &input_field)?;
+                sdl.push_str("  ");
+                write_input_value(sdl, &input_field)?;
+                sdl.push('\n');
+            }
+            sdl.push_str("}\n");
+        }
+        _ => return Err(invalid(format!("Unknown type kind '{kind}' of type '{name}'."))),
+    }
+    sdl.push('\n');
+    Ok(())
+}
+
+fn write_directive(sdl: &mut String, directive: &Bound<'_, PyAny>) -> PyResult<()> {
+    write_description(sdl, "", directive)?;
+    let _ = write!(sdl, "directive @{}", get_name(directive, "name")?);
+    write_arguments(sdl, directive)?;
+    let is_repeatable = get_optional(directive, "isRepeatable")?
+        .map(|value| value.extract::<bool>())
+        .transpose()?
+        .unwrap_or(false);
+    if is_repeatable {
+        sdl.push_str(" repeatable");
+    }
+    let locations = get_list(directive, "locations")?
+        .iter()
+        .map(|location| {
+            let location = location.extract::<String>()?;
+            if is_name(&location) {
+                Ok(location)
+            } else {
+                Err(invalid(format!("Invalid directive location '{location}'.")))
+            }
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let _ = writeln!(sdl, " on {}\n", locations.join(" | "));
+    Ok(())
+}
+
+/// Prints the schema described by an introspection result as SDL, like graphql-core's
+/// `build_client_schema` followed by `print_schema`. Accepts the result as dict or JSON text,
+/// with or without the surrounding `data`. Names and default values are checked before they are
+/// printed, raising `TypeError` like other malformed input instead of changing the schema.
+pub fn introspection_to_sdl(py: Python<'_>, introspection: &Bound<'_, PyAny>) -> PyResult<String> {
+    let introspection = if introspection.is_instance_of::<PyString>() {
+        py.import_bound("json")?.call_method1("loads", (introspection,))?
+    } else {
+        introspection.clone()
+    };
+    let introspection = match get_optional(&introspection, "data")? {
+        Some(data) => data,
+        None => introspection,
+    };
+    let schema = get(&introspection, "__schema")?;
+
+    let mut sdl = String::new();
+
+    let root_types = [
+        ("query", "queryType"),
+        ("mutation", "mutationType"),
+        ("subscription", "subscriptionType"),
+    ];
+    write_description(&mut sdl, "", &schema)?;
+    sdl.push_str("schema {\n");
+    for (operation_type, key) in root_types {
+        if let Some(root_type) = get_optional(&schema, key)? {
+            let _ = writeln!(sdl, "  {operation_type}: {}", get_name(&root_type, "name")?);
+        }
+    }
+    sdl.push_str("}\n\n");
+
+    for type_definition in get_list(&schema, "types")? {
+        let name = get_name(&type_definition, "name")?;
+        // apollo-compiler defines the built-in types and directives itself
+        if name.starts_with("__") || BUILT_IN_SCALARS.contains(&name.as_str()) {
+            continue;
+        }
+        write_type(&mut sdl, &type_definition)?;
+    }
+
+    for directive in get_list(&schema, "directives")? {
+        let name = get_name(&directive, "name")?;
+        if BUILT_IN_DIRECTIVES.contains(&name.as_str()) {
+            continue;
+        }
+        write_directive(&mut sdl, &directive)?;
+    }
+
+    Ok(sdl)
+}
@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::PathBuf;
+
+use pyo3::exceptions::{PyOSError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyMapping, PyString, PyTuple};
+
+pub mod diff;
+pub mod introspection;
+pub mod printer;
+
+/// Scalars and directives defined by the spec, which schemas include implicitly.
+pub const BUILT_IN_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+pub const BUILT_IN_DIRECTIVES: [&str; 4] = ["skip", "include", "deprecated", "specifiedBy"];
+
+/// Source name of a schema given as a single SDL text.
+const DEFAULT_SCHEMA_NAME: &str = "schema.graphql";
+
+/// The `(path, sdl)` sources of a schema given as SDL text, as mapping of file names to SDL text
+/// or as list or tuple of paths (`str` or `os.PathLike`) of files to read.
+pub fn schema_sources(schema: &Bound<'_, PyAny>) -> PyResult<Vec<(String, String)>> {
+    if let Ok(sdl) = schema.downcast::<PyString>() {
+        return Ok(vec![(DEFAULT_SCHEMA_NAME.to_string(), sdl.to_str()?.to_string())]);
+    }
+
+    if let Ok(files) = schema.downcast::<PyMapping>() {
+        return files
+            .items()?
+            .iter()?
+            .map(|item| {
+                item?.extract::<(String, String)>().map_err(|_| {
+                    PyTypeError::new_err("schema mappings must map file names to SDL text")
+                })
+            })
+            .collect();
+    }
+
+    let paths = if let Ok(paths) = schema.downcast::<PyList>() {
+        paths.iter().collect::<Vec<_>>()
+    } else if let Ok(paths) = schema.downcast::<PyTuple>() {
+        paths.iter().collect()
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "schema must be SDL text, a mapping of file names to SDL or a list of paths, not {}",
+            schema.get_type().name()?
+        )));
+    };
+    paths
+        .into_iter()
+        .map(|path| {
+            // Converted through os.fspath
+            let path = path.extract::<PathBuf>().map_err(|_| {
+                PyTypeError::new_err("schema paths must be str or os.PathLike")
+            })?;
+            let path = path.display().to_string();
+            let sdl = fs::read_to_string(&path).map_err(|error| {
+                PyOSError::new_err(format!("Could not read schema file '{path}': {error}"))
+            })?;
+            Ok((path, sdl))
+        })
+        .collect()
+}
@@ -0,0 +1,278 @@
+use std::fmt::Write;
+
+use apollo_compiler::executable::OperationType;
+use apollo_compiler::schema::{
+    DirectiveDefinition, DirectiveList, ExtendedType, FieldDefinition, InputValueDefinition,
+};
+use apollo_compiler::{Node, Schema};
+
+use crate::schema::{BUILT_IN_DIRECTIVES, BUILT_IN_SCALARS};
+
+/// Whether the type is defined by the spec rather than the schema.
+pub fn is_built_in_type(name: &str) -> bool {
+    name.starts_with("__") || BUILT_IN_SCALARS.contains(&name)
+}
+
+/// Sorts the items by name when `sorted` is set, and keeps their definition order otherwise.
+fn in_order<T>(mut items: Vec<T>, sorted: bool, name: impl Fn(&T) -> &str) -> Vec<T> {
+    if sorted {
+        items.sort_by(|a, b| name(a).cmp(name(b)));
+    }
+    items
+}
+
+/// Writes the description as block string. Like graphql-core's `print_block_string`, it only
+/// stays on one line if it ends in neither `"` nor `\`, which would run into the closing quotes.
+fn write_description(sdl: &mut String, indent: &str, description: Option<&str>) {
+    let Some(description) = description else {
+        return;
+    };
+    let description = description.replace("\"\"\"", "\\\"\"\"");
+    if description.contains('\n') || description.ends_with('"') || description.ends_with('\\') {
+        let _ = writeln!(sdl, "{indent}\"\"\"");
+        for line in description.lines() {
+            let _ = writeln!(sdl, "{indent}{line}");
+        }
+        let _ = writeln!(sdl, "{indent}\"\"\"");
+    } else {
+        let _ = writeln!(sdl, "{indent}\"\"\"{description}\"\"\"");
+    }
+}
+
+fn write_directives(sdl: &mut String, directives: &DirectiveList) {
+    for directive in directives.iter() {
+        let _ = write!(sdl, " {directive}");
+    }
+}
+
+fn write_input_value(sdl: &mut String, input_value: &InputValueDefinition) {
+    let _ = write!(sdl, "{}: {}", input_value.name, input_value.ty);
+    if let Some(default_value) = &input_value.default_value {
+        let _ = write!(sdl, " = {default_value}");
+    }
+    write_directives(sdl, &input_value.directives);
+}
+
+/// Arguments on one line, or one per line if any of them has a description, like graphql-core.
+fn write_arguments(
+    sdl: &mut String,
+    indent: &str,
+    arguments: &[Node<InputValueDefinition>],
+    sorted: bool,
+) {
+    if arguments.is_empty() {
+        return;
+    }
+    let arguments =
+        in_order(arguments.iter().collect(), sorted, |argument| argument.name.as_str());
+
+    if arguments.iter().all(|argument| argument.description.is_none()) {
+        sdl.push('(');
+        for (index, argument) in arguments.into_iter().enumerate() {
+            if index > 0 {
+                sdl.push_str(", ");
+            }
+            write_input_value(sdl, argument);
+        }
+        sdl.push(')');
+        return;
+    }
+
+    let argument_indent = format!("{indent}  ");
+    sdl.push_str("(\n");
+    for argument in arguments {
+        write_description(sdl, &argument_indent, argument.description.as_deref());
+        sdl.push_str(&argument_indent);
+        write_input_value(sdl, argument);
+        sdl.push('\n');
+    }
+    let _ = write!(sdl, "{indent})");
+}
+
+fn write_fields<'a>(
+    sdl: &mut String,
+    fields: impl Iterator<Item = &'a FieldDefinition>,
+    sorted: bool,
+) {
+    sdl.push_str(" {\n");
+    for field in in_order(fields.collect(), sorted, |field| field.name.as_str()) {
+        write_description(sdl, "  ", field.description.as_deref());
+        let _ = write!(sdl, "  {}", field.name);
+        write_arguments(sdl, "  ", &field.arguments, sorted);
+        let _ = write!(sdl, ": {}", field.ty);
+        write_directives(sdl, &field.directives);
+        sdl.push('\n');
+    }
+    sdl.push('}');
+}
+
+fn write_implements<'a>(
+    sdl: &mut String,
+    interfaces: impl Iterator<Item = &'a str>,
+    sorted: bool,
+) {
+    let interfaces = in_order(interfaces.collect(), sorted, |name| *name);
+    if !interfaces.is_empty() {
+        let _ = write!(sdl, " implements {}", interfaces.join(" & "));
+    }
+}
+
+fn write_type(sdl: &mut String, ty: &ExtendedType, sorted: bool) {
+    match ty {
+        ExtendedType::Scalar(scalar) => {
+            write_description(sdl, "", scalar.description.as_deref());
+            let _ = write!(sdl, "scalar {}", scalar.name);
+            write_directives(sdl, &scalar.directives);
+        }
+        ExtendedType::Object(object) => {
+            write_description(sdl, "", object.description.as_deref());
+            let _ = write!(sdl, "type {}", object.name);
+            write_implements(
+                sdl,
+                object.implements_interfaces.iter().map(|name| name.name.as_str()),
+                sorted,
+            );
+            write_directives(sdl, &object.directives);
+            write_fields(sdl, object.fields.values().map(|field| &**field), sorted);
+        }
+        ExtendedType::Interface(interface) => {
+            write_description(sdl, "", interface.description.as_deref());
+            let _ = write!(sdl, "interface {}", interface.name);
+            write_implements(
+                sdl,
+                interface.implements_interfaces.iter().map(|name| name.name.as_str()),
+                sorted,
+            );
+            write_directives(sdl, &interface.directives);
+            write_fields(sdl, interface.fields.values().map(|field| &**field), sorted);
+        }
+        ExtendedType::Union(union_type) => {
+            write_description(sdl, "", union_type.description.as_deref());
+            let _ = write!(sdl, "union {}", union_type.name);
+            write_directives(sdl, &union_type.directives);
+            let members = in_order(
+                union_type.members.iter().map(|name| name.name.as_str()).collect(),
+                sorted,
+                |name| *name,
+            );
+            if !members.is_empty() {
+                let _ = write!(sdl, " = {}", members.join(" | "));
+            }
+        }
+        ExtendedType::Enum(enum_type) => {
+            write_description(sdl, "", enum_type.description.as_deref());
+            let _ = write!(sdl, "enum {}", enum_type.name);
+            write_directives(sdl, &enum_type.directives);
+            sdl.push_str(" {\n");
+            let values = in_order(enum_type.values.values().collect(), sorted, |value| {
+                value.value.as_str()
+            });
+            for value in values {
+                write_description(sdl, "  ", value.description.as_deref());
+                let _ = write!(sdl, "  {}", value.value);
+                write_directives(sdl, &value.directives);
+                sdl.push('\n');
+            }
+            sdl.push('}');
+        }
+        ExtendedType::InputObject(input_object) => {
+            write_description(sdl, "", input_object.description.as_deref());
+            let _ = write!(sdl, "input {}", input_object.name);
+            write_directives(sdl, &input_object.directives);
+            sdl.push_str(" {\n");
+            let fields = in_order(input_object.fields.values().collect(), sorted, |field| {
+                field.name.as_str()
+            });
+            for field in fields {
+                write_description(sdl, "  ", field.description.as_deref());
+                sdl.push_str("  ");
+                write_input_value(sdl, field);
+                sdl.push('\n');
+            }
+            sdl.push('}');
+        }
+    }
+}
+
+fn write_directive_definition(sdl: &mut String, directive: &DirectiveDefinition, sorted: bool) {
+    write_description(sdl, "", directive.description.as_deref());
+    let _ = write!(sdl, "directive @{}", directive.name);
+    write_arguments(sdl, "", &directive.arguments, sorted);
+    if directive.repeatable {
+        sdl.push_str(" repeatable");
+    }
+    let locations: Vec<String> =
+        directive.locations.iter().map(|location| location.to_string()).collect();
+    let _ = write!(sdl, " on {}", locations.join(" | "));
+}
+
+/// The schema definition, omitted like graphql-core does when it would only repeat the
+/// conventional root type names.
+fn write_schema_definition(sdl: &mut String, schema: &Schema) -> bool {
+    let definition = &schema.schema_definition;
+    let root_types = [
+        (OperationType::Query, "query", "Query"),
+        (OperationType::Mutation, "mutation", "Mutation"),
+        (OperationType::Subscription, "subscription", "Subscription"),
+    ];
+    let is_conventional = root_types.iter().all(|(operation_type, _, conventional_name)| {
+        schema
+            .root_operation(*operation_type)
+            .map_or(true, |name| name.as_str() == *conventional_name)
+    });
+    if is_conventional && definition.description.is_none() && definition.directives.is_empty() {
+        return false;
+    }
+
+    write_description(sdl, "", definition.description.as_deref());
+    sdl.push_str("schema");
+    write_directives(sdl, &definition.directives);
+    sdl.push_str(" {\n");
+    for (operation_type, keyword, _) in root_types {
+        if let Some(name) = schema.root_operation(operation_type) {
+            let _ = writeln!(sdl, "  {keyword}: {name}");
+        }
+    }
+    sdl.push('}');
+    true
+}
+
+/// Prints the schema as SDL with extensions merged into their types, like graphql-core's
+/// `print_schema`. With `sorted`, directives, types and their members are ordered by name
+/// instead of by definition, giving a canonical form.
+pub fn print_schema(schema: &Schema, sorted: bool, include_builtins: bool) -> String {
+    let mut definitions = Vec::new();
+
+    let mut schema_definition = String::new();
+    if write_schema_definition(&mut schema_definition, schema) {
+        definitions.push(schema_definition);
+    }
+
+    let directives = schema
+        .directive_definitions
+        .values()
+        .filter(|directive| {
+            include_builtins || !BUILT_IN_DIRECTIVES.contains(&directive.name.as_str())
+        })
+        .collect();
+    for directive in in_order(directives, sorted, |directive| directive.name.as_str()) {
+        let mut sdl = String::new();
+        write_directive_definition(&mut sdl, directive, sorted);
+        definitions.push(sdl);
+    }
+
+    let types = schema
+        .types
+        .iter()
+        .filter(|(name, _)| include_builtins || !is_built_in_type(name.as_str()))
+        .collect();
+    for (_, ty) in in_order(types, sorted, |(name, _)| name.as_str()) {
+        let mut sdl = String::new();
+        write_type(&mut sdl, ty, sorted);
+        definitions.push(sdl);
+    }
+
+    let mut sdl = definitions.join("\n\n");
+    sdl.push('\n');
+    sdl
+}
@@ -0,0 +1,288 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Deref;
+
+use apollo_compiler::executable::{Field, Operation, OperationType, Selection, SelectionSet};
+use apollo_compiler::schema::{ExtendedType, Value};
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{ExecutableDocument, Node, Schema};
+
+use crate::ast::gql_core::error::GraphQLError;
+
+/// Arguments which, when present on a list field, give the expected number of items.
+const DEFAULT_SLICING_ARGUMENTS: [&str; 3] = ["first", "last", "limit"];
+
+/// Static cost of an operation: its total and the cost of every field by response path,
+/// including the cost of its selections.
+pub struct CostEstimate {
+    pub total: f64,
+    pub fields: BTreeMap<String, f64>,
+}
+
+/// Estimates the cost of `operation` before execution.
+///
+/// A field costs its `@cost(weight:)` from the schema, or by default 1 for fields of object,
+/// interface and union types and 0 for scalar and enum fields, plus the cost of its selections.
+/// List fields multiply this by the expected list size, taken from the slicing arguments named
+/// by `@listSize(slicingArguments:)` (or `first`, `last` and `limit`), else
+/// `@listSize(assumedSize:)`, else 1. `variables` resolves slicing arguments given as variables;
+/// variable defaults apply when a value is missing.
+pub fn estimate_cost(
+    schema: &Valid<Schema>,
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+    variables: &HashMap<String, f64>,
+) -> CostEstimate {
+    let variables = with_defaults(operation, variables);
+    estimate(schema, document, operation, variables, false)
+}
+
+/// `variables` completed with the numeric default values of the operation's variables.
+fn with_defaults(operation: &Operation, variables: &HashMap<String, f64>) -> HashMap<String, f64> {
+    let mut variables = variables.clone();
+    for variable in &operation.variables {
+        if variables.contains_key(variable.name.as_str()) {
+            continue;
+        }
+        let default_value = variable.default_value.as_ref().and_then(|value| number(value));
+        if let Some(default_value) = default_value {
+            variables.insert(variable.name.to_string(), default_value);
+        }
+    }
+    variables
+}
+
+/// Estimates the cost like `estimate_cost`. Slicing arguments given as variables without a value
+/// in `variables` are ignored, or make lists without an `assumedSize` unbounded if
+/// `unbounded_variables` is set.
+fn estimate(
+    schema: &Valid<Schema>,
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+    variables: HashMap<String, f64>,
+    unbounded_variables: bool,
+) -> CostEstimate {
+    let mut estimator = CostEstimator {
+        schema,
+        document,
+        variables,
+        unbounded_variables,
+        fragment_stack: Vec::new(),
+        fragment_costs: HashMap::new(),
+        fields: BTreeMap::new(),
+    };
+    let root_path = match &operation.name {
+        Some(name) => name.to_string(),
+        None => match operation.operation_type {
+            OperationType::Query => "query".to_string(),
+            OperationType::Mutation => "mutation".to_string(),
+            OperationType::Subscription => "subscription".to_string(),
+        },
+    };
+    let total = estimator.selection_set_cost(&operation.selection_set, &root_path);
+    CostEstimate {
+        total,
+        fields: estimator.fields,
+    }
+}
+
+/// Reports every operation whose estimated cost exceeds `max_cost`. Variable values are not
+/// known during validation, so slicing arguments given as variables take the variable's default
+/// value. Without one, the list has its `@listSize(assumedSize:)`, and is otherwise unbounded:
+/// clients choose the size freely, so a costly list makes the operation exceed any `max_cost`.
+pub fn check_cost(
+    schema: &Valid<Schema>,
+    document: &ExecutableDocument,
+    max_cost: f64,
+) -> Vec<GraphQLError> {
+    document
+        .all_operations()
+        .filter_map(|operation| {
+            let variables = with_defaults(operation, &HashMap::new());
+            let cost = estimate(schema, document, operation, variables, true).total;
+            if cost <= max_cost {
+                return None;
+            }
+            let message = if cost.is_infinite() {
+                format!(
+                    "Query cost exceeds the maximum allowed cost of {max_cost}, as list sizes \
+                     given by variables without a default value are unbounded"
+                )
+            } else {
+                format!("Query cost of {cost} exceeds the maximum allowed cost of {max_cost}")
+            };
+            Some(GraphQLError::new(
+                message,
+                operation.location(),
+                &document.sources,
+                Some("MaxCostExceeded"),
+            ))
+        })
+        .collect()
+}
+
+struct CostEstimator<'a> {
+    schema: &'a Valid<Schema>,
+    document: &'a ExecutableDocument,
+    variables: HashMap<String, f64>,
+    unbounded_variables: bool,
+    /// Fragments currently being expanded, to guard against cycles.
+    fragment_stack: Vec<&'a str>,
+    /// Cost of each fragment's selections once known, with the cost of its fields by path
+    /// relative to where the fragment is spread.
+    fragment_costs: HashMap<&'a str, (f64, BTreeMap<String, f64>)>,
+    fields: BTreeMap<String, f64>,
+}
+
+impl<'a> CostEstimator<'a> {
+    fn selection_set_cost(&mut self, selection_set: &SelectionSet, path: &str) -> f64 {
+        selection_set
+            .selections
+            .iter()
+            .map(|selection| self.selection_cost(selection, path))
+            .sum()
+    }
+
+    fn selection_cost(&mut self, selection: &Selection, path: &str) -> f64 {
+        match selection {
+            Selection::Field(field) => self.field_cost(field, path),
+            Selection::InlineFragment(inline_fragment) => {
+                self.selection_set_cost(&inline_fragment.selection_set, path)
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                let document = self.document;
+                let Some(fragment) = document.fragments.get(&fragment_spread.fragment_name) else {
+                    return 0.0;
+                };
+                let name = fragment.name.as_str();
+                if let Some((cost, fragment_fields)) = self.fragment_costs.get(name) {
+                    add_field_costs(&mut self.fields, path, fragment_fields);
+                    return *cost;
+                }
+                if self.fragment_stack.contains(&name) {
+                    return 0.0;
+                }
+                // Collect the fragment's field costs relative to the spread, so they can be
+                // added again wherever else it is spread
+                self.fragment_stack.push(name);
+                let fields = std::mem::take(&mut self.fields);
+                let cost = self.selection_set_cost(&fragment.selection_set, "");
+                let fragment_fields = std::mem::replace(&mut self.fields, fields);
+                self.fragment_stack.pop();
+                add_field_costs(&mut self.fields, path, &fragment_fields);
+                self.fragment_costs.insert(name, (cost, fragment_fields));
+                cost
+            }
+        }
+    }
+
+    fn field_cost(&mut self, field: &Node<Field>, path: &str) -> f64 {
+        let path = format!("{path}.{}", field.response_key());
+        let definition = &field.definition;
+
+        let weight = definition
+            .directives
+            .get("cost")
+            .and_then(|cost| cost.argument_by_name("weight"))
+            .and_then(|weight| number(weight))
+            .unwrap_or_else(|| {
+                let is_composite = matches!(
+                    self.schema.types.get(definition.ty.inner_named_type()),
+                    Some(
+                        ExtendedType::Object(_)
+                            | ExtendedType::Interface(_)
+                            | ExtendedType::Union(_)
+                    )
+                );
+                if is_composite && !field.name.as_str().starts_with("__") {
+                    1.0
+                } else {
+                    0.0
+                }
+            });
+
+        let selections_cost = self.selection_set_cost(&field.selection_set, &path);
+        let multiplier = if definition.ty.is_list() {
+            self.list_size(field)
+        } else {
+            1.0
+        };
+
+        // Lists of free items are free however long, which also keeps unbounded sizes from
+        // turning the cost into NaN
+        let item_cost = weight + selections_cost;
+        let cost = if item_cost == 0.0 {
+            0.0
+        } else {
+            multiplier * item_cost
+        };
+        *self.fields.entry(path).or_default() += cost;
+        cost
+    }
+
+    fn list_size(&self, field: &Node<Field>) -> f64 {
+        let list_size = field.definition.directives.get("listSize");
+
+        let slicing_arguments: Vec<&str> = match list_size
+            .and_then(|list_size| list_size.argument_by_name("slicingArguments"))
+            .map(|arguments| arguments.deref())
+        {
+            Some(Value::List(arguments)) => arguments
+                .iter()
+                .filter_map(|argument| argument.as_str())
+                .collect(),
+            _ => DEFAULT_SLICING_ARGUMENTS.to_vec(),
+        };
+
+        let slicing_values: Vec<&Node<Value>> = field
+            .arguments
+            .iter()
+            .filter(|argument| slicing_arguments.contains(&argument.name.as_str()))
+            .map(|argument| &argument.value)
+            .collect();
+        let sliced_size = slicing_values
+            .iter()
+            .filter_map(|value| self.resolve_number(value))
+            .reduce(f64::max);
+
+        sliced_size
+            .or_else(|| {
+                list_size
+                    .and_then(|list_size| list_size.argument_by_name("assumedSize"))
+                    .and_then(|assumed_size| number(assumed_size))
+            })
+            .unwrap_or_else(|| {
+                let sized_by_variable = slicing_values
+                    .iter()
+                    .any(|value| matches!(value.deref(), Value::Variable(_)));
+                if self.unbounded_variables && sized_by_variable {
+                    f64::INFINITY
+                } else {
+                    1.0
+                }
+            })
+    }
+
+    fn resolve_number(&self, value: &Node<Value>) -> Option<f64> {
+        match value.deref() {
+            Value::Variable(name) => self.variables.get(name.as_str()).copied(),
+            _ => number(value),
+        }
+    }
+}
+
+/// Adds the costs of `fields`, given by path relative to `path`, to the costs in `costs`.
+fn add_field_costs(costs: &mut BTreeMap<String, f64>, path: &str, fields: &BTreeMap<String, f64>) {
+    for (relative_path, cost) in fields {
+        *costs.entry(format!("{path}{relative_path}")).or_default() += cost;
+    }
+}
+
+/// The numeric value of an Int, Float or numeric String literal.
+fn number(value: &Node<Value>) -> Option<f64> {
+    match value.deref() {
+        Value::Int(int) => int.as_str().parse().ok(),
+        Value::Float(float) => float.as_str().parse().ok(),
+        Value::String(string) => string.as_str().parse().ok(),
+        _ => None,
+    }
+}
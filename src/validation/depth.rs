@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use apollo_compiler::executable::{Selection, SelectionSet};
+use apollo_compiler::ExecutableDocument;
+
+use crate::ast::gql_core::error::GraphQLError;
+
+/// Reports operations nesting fields deeper than `max_depth`, following fragments.
+/// Root fields have depth 0 and introspection fields are ignored, matching Strawberry's
+/// `QueryDepthLimiter`. Each operation is reported once, at the first field past the limit,
+/// like graphql-depth-limit.
+pub fn check_depth(document: &ExecutableDocument, max_depth: usize) -> Vec<GraphQLError> {
+    let mut errors = Vec::new();
+    let mut checker = DepthChecker {
+        document,
+        operation_name: "anonymous",
+        max_depth,
+        fragment_stack: Vec::new(),
+        fragment_depths: HashMap::new(),
+        error: None,
+    };
+    for operation in document.all_operations() {
+        checker.operation_name = operation
+            .name
+            .as_ref()
+            .map_or("anonymous", |name| name.as_str());
+        checker.selection_set_depth(&operation.selection_set, 0);
+        errors.extend(checker.error.take());
+    }
+    errors
+}
+
+struct DepthChecker<'doc> {
+    document: &'doc ExecutableDocument,
+    operation_name: &'doc str,
+    max_depth: usize,
+    /// Fragments currently being expanded, to guard against cycles.
+    fragment_stack: Vec<&'doc str>,
+    /// Depth of each fragment's deepest field relative to where it is spread, once known.
+    /// Shared between operations since it does not depend on the operation.
+    fragment_depths: HashMap<&'doc str, usize>,
+    /// The error for the first field past `max_depth`, once one was found.
+    error: Option<GraphQLError>,
+}
+
+impl<'doc> DepthChecker<'doc> {
+    fn selection_set_depth(&mut self, selection_set: &'doc SelectionSet, depth: usize) -> usize {
+        selection_set
+            .selections
+            .iter()
+            .map(|selection| self.selection_depth(selection, depth))
+            .max()
+            .unwrap_or(depth)
+    }
+
+    fn selection_depth(&mut self, selection: &'doc Selection, depth: usize) -> usize {
+        // Only the first field past the limit is reported, so there is nothing left to find.
+        if self.error.is_some() {
+            return depth;
+        }
+        match selection {
+            Selection::Field(field) => {
+                if field.name.as_str().starts_with("__") {
+                    return 0;
+                }
+                if depth > self.max_depth {
+                    self.error = Some(GraphQLError::new(
+                        format!(
+                            "'{}' exceeds maximum operation depth of {}",
+                            self.operation_name, self.max_depth
+                        ),
+                        field.location(),
+                        &self.document.sources,
+                        Some("MaxDepthExceeded"),
+                    ));
+                    return depth;
+                }
+                if field.selection_set.selections.is_empty() {
+                    return depth;
+                }
+                self.selection_set_depth(&field.selection_set, depth + 1)
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                self.selection_set_depth(&inline_fragment.selection_set, depth)
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                let name = fragment_spread.fragment_name.as_str();
+                let Some(fragment) = self.document.fragments.get(&fragment_spread.fragment_name)
+                else {
+                    return depth;
+                };
+                if let Some(&relative_depth) = self.fragment_depths.get(name) {
+                    // Only walk the fragment again if it goes past the limit here, to find the
+                    // field to report.
+                    if depth + relative_depth <= self.max_depth {
+                        return depth + relative_depth;
+                    }
+                }
+                if self.fragment_stack.contains(&name) {
+                    return depth;
+                }
+                self.fragment_stack.push(name);
+                let fragment_depth = self.selection_set_depth(&fragment.selection_set, depth);
+                self.fragment_stack.pop();
+                if self.error.is_none() {
+                    self.fragment_depths
+                        .insert(name, fragment_depth.saturating_sub(depth));
+                }
+                fragment_depth
+            }
+        }
+    }
+}
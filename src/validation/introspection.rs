@@ -0,0 +1,48 @@
+use apollo_compiler::executable::{Selection, SelectionSet};
+use apollo_compiler::ExecutableDocument;
+
+use crate::ast::gql_core::error::GraphQLError;
+
+/// Reports every introspection meta-field except `__typename`, in operations and fragments,
+/// like graphql-core's `NoSchemaIntrospectionCustomRule`.
+pub fn check_introspection(document: &ExecutableDocument) -> Vec<GraphQLError> {
+    let mut errors = Vec::new();
+    let selection_sets = document
+        .all_operations()
+        .map(|operation| &operation.selection_set)
+        .chain(document.fragments.values().map(|fragment| &fragment.selection_set));
+    for selection_set in selection_sets {
+        check_selection_set(document, selection_set, &mut errors);
+    }
+    errors
+}
+
+fn check_selection_set(
+    document: &ExecutableDocument,
+    selection_set: &SelectionSet,
+    errors: &mut Vec<GraphQLError>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                let name = field.name.as_str();
+                if name.starts_with("__") && name != "__typename" {
+                    errors.push(GraphQLError::new(
+                        format!(
+                            "GraphQL introspection has been disabled, but the requested query contained the field '{name}'."
+                        ),
+                        field.location(),
+                        &document.sources,
+                        Some("IntrospectionDisabled"),
+                    ));
+                }
+                check_selection_set(document, &field.selection_set, errors);
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                check_selection_set(document, &inline_fragment.selection_set, errors)
+            }
+            // Fragment definitions are checked on their own
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use apollo_compiler::executable::{Field, Selection, SelectionSet};
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{ExecutableDocument, Node, Parser, Schema};
+
+use crate::ast::gql_core::error::GraphQLError;
+
+/// Limits applied while parsing a document, to reject abusive documents before validation.
+/// All limits are disabled by default.
+#[derive(Clone, Default)]
+pub struct ParseLimits {
+    /// Maximum number of tokens, enforced by apollo-parser.
+    pub max_tokens: Option<usize>,
+    /// Maximum nesting of selection sets and values, enforced by apollo-parser.
+    pub max_recursion: Option<usize>,
+    /// Maximum number of aliased fields in the whole document.
+    pub max_aliases: Option<usize>,
+    /// Maximum number of root fields per operation, counting fields selected through fragments.
+    pub max_root_fields: Option<usize>,
+    /// Maximum number of directives on a single field.
+    pub max_directives_per_field: Option<usize>,
+}
+
+impl ParseLimits {
+    fn parser(&self) -> Parser {
+        let mut parser = Parser::new();
+        if let Some(max_tokens) = self.max_tokens {
+            parser = parser.token_limit(max_tokens);
+        }
+        if let Some(max_recursion) = self.max_recursion {
+            parser = parser.recursion_limit(max_recursion);
+        }
+        parser
+    }
+
+    /// Parses the document with the token and recursion limits. Errors for exceeding them get
+    /// the codes `MaxTokensExceeded` and `MaxRecursionExceeded`, like the limits of `check`.
+    pub fn parse(
+        &self,
+        schema: &Valid<Schema>,
+        source: &str,
+        source_name: &str,
+    ) -> Result<ExecutableDocument, Vec<GraphQLError>> {
+        self.parser()
+            .parse_executable(schema, source, source_name)
+            .map_err(|with_errors| {
+                let mut errors = GraphQLError::from_diagnostics(&with_errors.errors);
+                for error in &mut errors {
+                    // apollo-parser only tells its limits apart by the message
+                    let message = &error.compiler_error.message;
+                    if message.contains("token limit reached") {
+                        error.code = Some("MaxTokensExceeded".to_string());
+                    } else if message.contains("recursion limit reached") {
+                        error.code = Some("MaxRecursionExceeded".to_string());
+                    }
+                }
+                errors
+            })
+    }
+
+    /// Checks the limits apollo-parser does not know about on a parsed document.
+    pub fn check(&self, document: &ExecutableDocument) -> Vec<GraphQLError> {
+        let mut errors = Vec::new();
+
+        let selection_sets = document
+            .all_operations()
+            .map(|operation| &operation.selection_set)
+            .chain(document.fragments.values().map(|fragment| &fragment.selection_set));
+        let mut fields = Vec::new();
+        for selection_set in selection_sets {
+            collect_fields(selection_set, &mut fields);
+        }
+
+        if let Some(max_aliases) = self.max_aliases {
+            let mut aliased = fields.iter().filter(|field| field.alias.is_some());
+            if let Some(first_excess) = aliased.nth(max_aliases) {
+                let aliases = max_aliases + 1 + aliased.count();
+                errors.push(GraphQLError::new(
+                    format!(
+                        "Document contains {aliases} aliases, exceeding the maximum of {max_aliases}"
+                    ),
+                    first_excess.location(),
+                    &document.sources,
+                    Some("MaxAliasesExceeded"),
+                ));
+            }
+        }
+
+        if let Some(max_directives) = self.max_directives_per_field {
+            for field in &fields {
+                let directives = field.directives.len();
+                if directives > max_directives {
+                    errors.push(GraphQLError::new(
+                        format!(
+                            "Field '{}' has {directives} directives, exceeding the maximum of {max_directives}",
+                            field.name
+                        ),
+                        field.location(),
+                        &document.sources,
+                        Some("MaxDirectivesExceeded"),
+                    ));
+                }
+            }
+        }
+
+        if let Some(max_root_fields) = self.max_root_fields {
+            for operation in document.all_operations() {
+                let mut visited = HashSet::new();
+                let root_fields =
+                    count_root_fields(document, &operation.selection_set, &mut visited);
+                if root_fields > max_root_fields {
+                    let operation_name = operation
+                        .name
+                        .as_ref()
+                        .map_or("anonymous", |name| name.as_str());
+                    errors.push(GraphQLError::new(
+                        format!(
+                            "Operation '{operation_name}' selects {root_fields} root fields, exceeding the maximum of {max_root_fields}"
+                        ),
+                        operation.location(),
+                        &document.sources,
+                        Some("MaxRootFieldsExceeded"),
+                    ));
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+/// All fields written in the selection set, without following fragment spreads.
+fn collect_fields<'doc>(
+    selection_set: &'doc SelectionSet,
+    fields: &mut Vec<&'doc Node<Field>>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                fields.push(field);
+                collect_fields(&field.selection_set, fields);
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                collect_fields(&inline_fragment.selection_set, fields)
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+/// Number of fields selected directly by the selection set, including those selected through
+/// inline fragments and fragment spreads. Each fragment is counted once, which also guards
+/// against the fragment cycles an unvalidated document may contain.
+fn count_root_fields<'doc>(
+    document: &'doc ExecutableDocument,
+    selection_set: &'doc SelectionSet,
+    visited: &mut HashSet<&'doc str>,
+) -> usize {
+    selection_set
+        .selections
+        .iter()
+        .map(|selection| match selection {
+            Selection::Field(_) => 1,
+            Selection::InlineFragment(inline_fragment) => {
+                count_root_fields(document, &inline_fragment.selection_set, visited)
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                match document.fragments.get(&fragment_spread.fragment_name) {
+                    Some(fragment) if visited.insert(fragment.name.as_str()) => {
+                        count_root_fields(document, &fragment.selection_set, visited)
+                    }
+                    _ => 0,
+                }
+            }
+        })
+        .sum()
+}
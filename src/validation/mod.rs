@@ -0,0 +1,44 @@
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{ExecutableDocument, Schema};
+
+use crate::ast::gql_core::error::GraphQLError;
+
+pub mod cost;
+pub mod depth;
+pub mod introspection;
+pub mod limits;
+pub mod python_rules;
+
+/// Checks run in addition to apollo-compiler's validation. All are disabled by default.
+#[derive(Clone, Default)]
+pub struct ValidationOptions {
+    pub max_depth: Option<usize>,
+    pub max_cost: Option<f64>,
+    pub disable_introspection: bool,
+}
+
+/// Validates the document against the schema. The additional checks enabled in `options`
+/// only run once apollo-compiler's validation passed, so they may rely on a well-formed document.
+/// Returns the validated document, `None` if apollo-compiler's validation failed, and all errors.
+pub fn validate_document(
+    schema: &Valid<Schema>,
+    document: ExecutableDocument,
+    options: &ValidationOptions,
+) -> (Option<Valid<ExecutableDocument>>, Vec<GraphQLError>) {
+    let document = match document.validate(schema) {
+        Ok(document) => document,
+        Err(with_errors) => return (None, GraphQLError::from_diagnostics(&with_errors.errors)),
+    };
+
+    let mut errors = Vec::new();
+    if options.disable_introspection {
+        errors.extend(introspection::check_introspection(&document));
+    }
+    if let Some(max_depth) = options.max_depth {
+        errors.extend(depth::check_depth(&document, max_depth));
+    }
+    if let Some(max_cost) = options.max_cost {
+        errors.extend(cost::check_cost(schema, &document, max_cost));
+    }
+    (Some(document), errors)
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use apollo_compiler::executable::{DirectiveList, Field, OperationType, Selection, SelectionSet};
+use apollo_compiler::{ExecutableDocument, Node, NodeLocation, SourceMap};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::ast::gql_core::error::GraphQLError;
+
+/// Visitor methods a Python rule may define. Each is called as `method(node, context)`.
+const VISITOR_METHODS: [&str; 10] = [
+    "enter_operation_definition",
+    "leave_operation_definition",
+    "enter_fragment_definition",
+    "leave_fragment_definition",
+    "enter_field",
+    "leave_field",
+    "enter_fragment_spread",
+    "leave_fragment_spread",
+    "enter_inline_fragment",
+    "leave_inline_fragment",
+];
+
+/// A node of the document as seen by Python rules. Attributes not applicable to the node's
+/// `kind` are `None`.
+#[pyclass(frozen)]
+pub struct VisitedNode {
+    /// graphql-core's node kind, e.g. `field` or `fragment_spread`.
+    #[pyo3(get)]
+    kind: &'static str,
+    /// Field, fragment or operation name.
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    alias: Option<String>,
+    /// Name of the type the field is selected on.
+    #[pyo3(get)]
+    parent_type: Option<String>,
+    /// Printed type of a field, or the type condition of a fragment.
+    #[pyo3(get)]
+    r#type: Option<String>,
+    /// `query`, `mutation` or `subscription` for operations.
+    #[pyo3(get)]
+    operation: Option<&'static str>,
+    /// Argument names mapped to their printed GraphQL values.
+    #[pyo3(get)]
+    arguments: Py<PyDict>,
+    #[pyo3(get)]
+    directives: Vec<String>,
+    #[pyo3(get)]
+    is_deprecated: bool,
+    #[pyo3(get)]
+    deprecation_reason: Option<String>,
+    location: Option<NodeLocation>,
+}
+
+impl VisitedNode {
+    fn new(py: Python<'_>, kind: &'static str, location: Option<NodeLocation>) -> Self {
+        Self {
+            kind,
+            name: None,
+            alias: None,
+            parent_type: None,
+            r#type: None,
+            operation: None,
+            arguments: PyDict::new_bound(py).unbind(),
+            directives: Vec::new(),
+            is_deprecated: false,
+            deprecation_reason: None,
+            location,
+        }
+    }
+}
+
+/// Passed to every visitor method to report errors.
+#[pyclass]
+pub struct ValidationContext {
+    sources: SourceMap,
+    errors: Vec<GraphQLError>,
+}
+
+#[pymethods]
+impl ValidationContext {
+    /// Reports an error, located at `node` if given.
+    #[pyo3(signature = (message, node = None, code = None))]
+    fn report_error(
+        &mut self,
+        message: String,
+        node: Option<&Bound<'_, VisitedNode>>,
+        code: Option<&str>,
+    ) {
+        let location = node.and_then(|node| node.get().location);
+        self.errors.push(GraphQLError::new(message, location, &self.sources, code));
+    }
+}
+
+/// Runs the Python rules over the document in a single traversal and returns their errors.
+/// Fragment spreads are not followed, fragment definitions are visited on their own.
+pub fn run_python_rules(
+    py: Python<'_>,
+    document: &ExecutableDocument,
+    rules: &[Bound<'_, PyAny>],
+) -> PyResult<Vec<GraphQLError>> {
+    let mut methods: HashMap<&'static str, Vec<Bound<PyAny>>> = HashMap::new();
+    for rule in rules {
+        for method_name in VISITOR_METHODS {
+            if rule.hasattr(method_name)? {
+                methods
+                    .entry(method_name)
+                    .or_default()
+                    .push(rule.getattr(method_name)?);
+            }
+        }
+    }
+
+    let context = Bound::new(
+        py,
+        ValidationContext {
+            sources: document.sources.clone(),
+            errors: Vec::new(),
+        },
+    )?;
+    let visitor = RuleVisitor {
+        py,
+        document,
+        methods,
+        context: &context,
+    };
+
+    for operation in document.all_operations() {
+        let mut node = VisitedNode::new(py, "operation_definition", operation.location());
+        node.name = operation.name.as_ref().map(|name| name.to_string());
+        node.operation = Some(match operation.operation_type {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        });
+        node.directives = directive_names(&operation.directives);
+        visitor.visit("operation_definition", node, |visitor| {
+            visitor.visit_selection_set(&operation.selection_set)
+        })?;
+    }
+
+    for fragment in document.fragments.values() {
+        let mut node = VisitedNode::new(py, "fragment_definition", fragment.location());
+        node.name = Some(fragment.name.to_string());
+        node.r#type = Some(fragment.type_condition().to_string());
+        node.directives = directive_names(&fragment.directives);
+        visitor.visit("fragment_definition", node, |visitor| {
+            visitor.visit_selection_set(&fragment.selection_set)
+        })?;
+    }
+
+    let errors = std::mem::take(&mut context.borrow_mut().errors);
+    Ok(errors)
+}
+
+fn directive_names(directives: &DirectiveList) -> Vec<String> {
+    directives
+        .iter()
+        .map(|directive| directive.name.to_string())
+        .collect()
+}
+
+struct RuleVisitor<'py, 'a> {
+    py: Python<'py>,
+    document: &'a ExecutableDocument,
+    methods: HashMap<&'static str, Vec<Bound<'py, PyAny>>>,
+    context: &'a Bound<'py, ValidationContext>,
+}
+
+impl RuleVisitor<'_, '_> {
+    /// Calls the `enter_` methods for `node`, visits its children and calls the `leave_` methods.
+    fn visit(
+        &self,
+        kind: &'static str,
+        node: VisitedNode,
+        children: impl FnOnce(&Self) -> PyResult<()>,
+    ) -> PyResult<()> {
+        let enter = self.methods.get(format!("enter_{kind}").as_str());
+        let leave = self.methods.get(format!("leave_{kind}").as_str());
+        if enter.is_none() && leave.is_none() {
+            return children(self);
+        }
+
+        let node = Bound::new(self.py, node)?;
+        for method in enter.into_iter().flatten() {
+            method.call1((&node, self.context))?;
+        }
+        children(self)?;
+        for method in leave.into_iter().flatten() {
+            method.call1((&node, self.context))?;
+        }
+        Ok(())
+    }
+
+    fn visit_selection_set(&self, selection_set: &SelectionSet) -> PyResult<()> {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    let node = self.field_node(field, selection_set.ty.as_str())?;
+                    self.visit("field", node, |visitor| {
+                        visitor.visit_selection_set(&field.selection_set)
+                    })?;
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let mut node =
+                        VisitedNode::new(self.py, "fragment_spread", fragment_spread.location());
+                    node.name = Some(fragment_spread.fragment_name.to_string());
+                    node.r#type = self
+                        .document
+                        .fragments
+                        .get(&fragment_spread.fragment_name)
+                        .map(|fragment| fragment.type_condition().to_string());
+                    node.directives = directive_names(&fragment_spread.directives);
+                    self.visit("fragment_spread", node, |_| Ok(()))?;
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    let mut node =
+                        VisitedNode::new(self.py, "inline_fragment", inline_fragment.location());
+                    node.r#type = inline_fragment
+                        .type_condition
+                        .as_ref()
+                        .map(|type_condition| type_condition.to_string());
+                    node.directives = directive_names(&inline_fragment.directives);
+                    self.visit("inline_fragment", node, |visitor| {
+                        visitor.visit_selection_set(&inline_fragment.selection_set)
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn field_node(&self, field: &Node<Field>, parent_type: &str) -> PyResult<VisitedNode> {
+        let mut node = VisitedNode::new(self.py, "field", field.location());
+        node.name = Some(field.name.to_string());
+        node.alias = field.alias.as_ref().map(|alias| alias.to_string());
+        node.parent_type = Some(parent_type.to_string());
+        node.r#type = Some(field.definition.ty.to_string());
+        node.directives = directive_names(&field.directives);
+
+        let arguments = node.arguments.bind(self.py);
+        for argument in &field.arguments {
+            arguments.set_item(argument.name.as_str(), argument.value.to_string())?;
+        }
+
+        if let Some(deprecated) = field.definition.directives.get("deprecated") {
+            node.is_deprecated = true;
+            node.deprecation_reason = Some(
+                deprecated
+                    .argument_by_name("reason")
+                    .and_then(|reason| reason.as_str())
+                    .unwrap_or("No longer supported")
+                    .to_string(),
+            );
+        }
+        Ok(node)
+    }
+}
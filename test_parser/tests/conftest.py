@@ -0,0 +1,16 @@
+SCHEMA = """
+type Query {
+  name: String
+  user: User
+  users: [User]
+}
+
+type User {
+  name: String
+  friends: [User]
+}
+"""
+
+
+def codes(errors):
+    return [error.extensions["code"] for error in errors]
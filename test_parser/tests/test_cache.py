@@ -0,0 +1,91 @@
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  name: String
+  age: Int
+}
+"""
+
+
+def test_cache_disabled_by_default():
+    compiler = QueryCompiler(SCHEMA)
+    compiler.parse("{ name }")
+    compiler.parse("{ name }")
+
+    info = compiler.cache_info()
+    assert (info.hits, info.misses, info.maxsize, info.currsize) == (0, 0, 0, 0)
+
+
+def test_cache_hits_and_misses():
+    compiler = QueryCompiler(SCHEMA, cache_size=2)
+    compiler.parse("{ name }")
+    compiler.parse("{ name }")
+    compiler.parse("{ age }")
+
+    info = compiler.cache_info()
+    assert (info.hits, info.misses, info.maxsize, info.currsize) == (1, 2, 2, 2)
+
+
+def test_source_name_is_part_of_the_key():
+    compiler = QueryCompiler(SCHEMA, cache_size=2)
+    compiler.parse("{ name }", source_name="a.graphql")
+    compiler.parse("{ name }", source_name="b.graphql")
+
+    assert compiler.cache_info().misses == 2
+
+
+def test_least_recently_used_entry_is_evicted():
+    compiler = QueryCompiler(SCHEMA, cache_size=2)
+    compiler.parse("{ name }")
+    compiler.parse("{ age }")
+    compiler.parse("{ name }")
+    compiler.parse("{ name age }")
+    compiler.parse("{ name }")
+    compiler.parse("{ age }")
+
+    info = compiler.cache_info()
+    assert (info.hits, info.misses, info.currsize) == (2, 4, 2)
+
+
+def test_validation_errors_are_cached_per_source():
+    compiler = QueryCompiler(SCHEMA, cache_size=1)
+    invalid = compiler.parse("{ unknown }")
+    assert len(compiler.validation_errors(invalid)) == 1
+
+    # Evicts the entry of the invalid document
+    valid = compiler.parse("{ name }")
+    assert compiler.validation_errors(valid) == []
+    assert len(compiler.validation_errors(invalid)) == 1
+    assert compiler.validation_errors(valid) == []
+
+
+def test_validation_with_overrides_is_not_cached():
+    compiler = QueryCompiler(SCHEMA, cache_size=1)
+    document = compiler.parse("{ name }")
+
+    assert compiler.validate(document)
+    assert not compiler.validate(document, max_cost=-1)
+    assert compiler.validate(document)
+
+
+def test_cache_ast():
+    compiler = QueryCompiler(SCHEMA, cache_size=1, cache_ast=True)
+
+    first = compiler.gql_core_ast_mirror(compiler.parse("{ name }"))
+    second = compiler.gql_core_ast_mirror(compiler.parse("{ name }"))
+    other = compiler.gql_core_ast_mirror(compiler.parse("{ age }"))
+
+    assert first is second
+    assert other is not first
+    assert other.definitions[0].selection_set.selections[0].name.value == "age"
+
+
+def test_cache_clear():
+    compiler = QueryCompiler(SCHEMA, cache_size=2)
+    compiler.parse("{ name }")
+    compiler.parse("{ name }")
+    compiler.cache_clear()
+
+    info = compiler.cache_info()
+    assert (info.hits, info.misses, info.currsize) == (0, 0, 0)
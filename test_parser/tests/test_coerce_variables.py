@@ -0,0 +1,112 @@
+from types import MappingProxyType
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+enum Color {
+  RED
+  GREEN
+}
+
+input Inner {
+  size: Int!
+}
+
+input Filter {
+  name: String!
+  color: Color = RED
+  ratio: Float = 1
+  inner: Inner
+}
+
+type Query {
+  search(filter: Filter, ids: [Int!], limit: Int): [String]
+}
+"""
+
+QUERY = "query Search($f: Filter!, $ids: [Int!]) { search(filter: $f, ids: $ids) }"
+
+
+def coerce(variables, query=QUERY):
+    compiler = QueryCompiler(SCHEMA)
+    return compiler.coerce_variables(compiler.parse(query), None, variables)
+
+
+def messages(variables, query=QUERY):
+    return [error.message for error in coerce(variables, query)]
+
+
+def test_coerces_values_and_field_defaults():
+    coerced = coerce({"f": {"name": "a"}, "ids": 1})
+
+    assert coerced == {"f": {"name": "a", "color": "RED", "ratio": 1.0}, "ids": [1]}
+    assert isinstance(coerced["f"]["ratio"], float)
+
+
+def test_accepts_any_mapping():
+    assert coerce({"f": MappingProxyType({"name": "a"})})["f"]["name"] == "a"
+    assert messages({"f": ["a"]}) == [
+        "Variable '$f' got invalid value ['a']; Expected type 'Filter' to be a mapping."
+    ]
+
+
+def test_required_variable():
+    assert messages({}) == ["Variable '$f' of required type 'Filter!' was not provided."]
+
+
+def test_null_variable():
+    assert messages({"f": None}) == ["Variable '$f' of non-null type 'Filter!' must not be null."]
+
+
+def test_required_field():
+    assert messages({"f": {}}) == [
+        "Variable '$f' got invalid value {}; Field 'name' of required type 'String!' was not provided."
+    ]
+
+
+def test_undefined_field():
+    assert messages({"f": {"name": "a", "size": 1}}) == [
+        "Variable '$f' got invalid value {'name': 'a', 'size': 1}; "
+        "Field 'size' is not defined by type 'Filter'."
+    ]
+
+
+def test_list_item_path():
+    assert messages({"f": {"name": "a"}, "ids": [1, None]}) == [
+        "Variable '$ids' got invalid value None at 'ids[1]'; "
+        "Expected non-nullable type 'Int!' not to be null."
+    ]
+
+
+def test_nested_path():
+    assert messages({"f": {"name": "a", "inner": {"size": "big"}}}) == [
+        "Variable '$f' got invalid value 'big' at 'f.inner.size'; "
+        "Int cannot represent non-integer value: 'big'"
+    ]
+
+
+def test_enum_values():
+    assert messages({"f": {"name": "a", "color": "BLUE"}}) == [
+        "Variable '$f' got invalid value 'BLUE' at 'f.color'; "
+        "Value 'BLUE' does not exist in 'Color' enum."
+    ]
+    assert messages({"f": {"name": "a", "color": 1}}) == [
+        "Variable '$f' got invalid value 1 at 'f.color'; "
+        "Enum 'Color' cannot represent non-string value: 1."
+    ]
+
+
+def test_variable_defaults():
+    query = "query Search($limit: Int = 3) { search(limit: $limit) }"
+
+    assert coerce({}, query) == {"limit": 3}
+    assert coerce({"limit": None}, query) == {"limit": None}
+
+
+def test_invalid_variable_default():
+    query = 'query Search($limit: Int = "x") { search(limit: $limit) }'
+
+    assert messages({}, query) == [
+        "Variable '$limit' has invalid default value 'x'; "
+        "Int cannot represent non-integer value: 'x'"
+    ]
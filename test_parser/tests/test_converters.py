@@ -0,0 +1,90 @@
+import pytest
+from graphql import parse, print_ast
+from graphql.language.ast import (
+    FragmentDefinitionNode,
+    FragmentSpreadNode,
+    InlineFragmentNode,
+    OperationDefinitionNode,
+)
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+directive @tag(name: String) on QUERY | FRAGMENT_DEFINITION | VARIABLE_DEFINITION
+
+type Query {
+  user(id: ID, filter: Filter): User
+}
+
+input Filter {
+  names: [String!]
+  active: Boolean
+}
+
+type User {
+  name: String
+  score: Float
+}
+"""
+
+QUERIES = [
+    "{ user { name } }",
+    "query Q($id: ID! = 1 @tag, $ids: [[ID]!]) @tag(name: \"q\") { user(id: $id) { a: name } }",
+    '{ user(filter: {names: ["a", "b"], active: null}) { name score } }',
+    "query Q { user { ...F ... on User { name } ... @skip(if: false) { score } } } "
+    "fragment F on User @tag { name }",
+]
+
+
+@pytest.mark.parametrize("query", QUERIES)
+def test_legacy_converter_matches_graphql_core(query):
+    compiler = QueryCompiler(SCHEMA)
+
+    ast = compiler.gql_core_ast(compiler.parse(query))
+
+    assert print_ast(ast) == print_ast(parse(query))
+
+
+def convert(query, mirror):
+    compiler = QueryCompiler(SCHEMA)
+    document = compiler.parse(query)
+    return compiler.gql_core_ast_mirror(document) if mirror else compiler.gql_core_ast(document)
+
+
+@pytest.mark.parametrize("mirror", [False, True])
+def test_fragment_spreads_and_inline_fragments(mirror):
+    ast = convert(QUERIES[3], mirror)
+
+    user = ast.definitions[0].selection_set.selections[0]
+    spread, typed, untyped = user.selection_set.selections
+
+    assert isinstance(spread, FragmentSpreadNode)
+    assert spread.name.value == "F"
+    assert isinstance(typed, InlineFragmentNode)
+    assert typed.type_condition.name.value == "User"
+    assert [field.name.value for field in typed.selection_set.selections] == ["name"]
+    assert isinstance(untyped, InlineFragmentNode)
+    assert untyped.type_condition is None
+    assert [directive.name.value for directive in untyped.directives] == ["skip"]
+    assert untyped.directives[0].arguments[0].value.value is False
+
+
+@pytest.mark.parametrize("mirror", [False, True])
+def test_definitions_in_source_order(mirror):
+    query = (
+        "fragment A on User { name } "
+        "query Q { user { ...A ...B } } "
+        "fragment B on User { score }"
+    )
+
+    ast = convert(query, mirror)
+
+    assert [
+        (definition.__class__, definition.name.value)
+        for definition in ast.definitions
+    ] == [
+        (FragmentDefinitionNode, "A"),
+        (OperationDefinitionNode, "Q"),
+        (FragmentDefinitionNode, "B"),
+    ]
+    assert ast.definitions[2].type_condition.name.value == "User"
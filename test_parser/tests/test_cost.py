@@ -0,0 +1,128 @@
+from conftest import codes
+from rustberry import QueryCompiler
+
+SCHEMA = """
+directive @cost(weight: Int!) on FIELD_DEFINITION
+directive @listSize(assumedSize: Int, slicingArguments: [String!]) on FIELD_DEFINITION
+
+type Query {
+  users(first: Int): [User]
+  search(count: Int): [User] @listSize(slicingArguments: ["count"])
+  featured(first: Int): [User] @listSize(assumedSize: 5)
+  expensive: User @cost(weight: 10)
+  tags(first: Int): [String]
+}
+
+type User {
+  name: String
+  friends(first: Int): [User]
+}
+"""
+
+
+def estimate(query, variables=None, **kwargs):
+    compiler = QueryCompiler(SCHEMA, **kwargs)
+    return compiler.estimate_cost(compiler.parse(query), variables)
+
+
+def test_list_size_from_slicing_argument():
+    cost = estimate("{ users(first: 3) { name friends(first: 2) { name } } }")
+
+    assert cost["total"] == 9
+    assert cost["fields"] == {
+        "query.users": 9,
+        "query.users.name": 0,
+        "query.users.friends": 2,
+        "query.users.friends.name": 0,
+    }
+
+
+def test_list_size_directive():
+    assert estimate('{ search(count: 4) { name } }')["total"] == 4
+    assert estimate("{ featured { name } }")["total"] == 5
+
+
+def test_cost_directive():
+    assert estimate("{ expensive { name } }")["total"] == 10
+
+
+def test_list_size_from_variables():
+    query = "query Users($n: Int = 4) { users(first: $n) { name } }"
+
+    assert estimate(query, {"n": 10})["total"] == 10
+    assert estimate(query)["total"] == 4
+    assert estimate(query)["fields"]["Users.users"] == 4
+
+
+def test_cyclic_fragments_terminate():
+    cost = estimate("{ expensive { ...A } } fragment A on User { name ...A }")
+
+    assert cost["total"] == 10
+
+
+def test_fragment_costs_at_each_spread():
+    cost = estimate(
+        "{ expensive { ...F } users(first: 2) { ...F } } "
+        "fragment F on User { friends(first: 3) { name } }"
+    )
+
+    assert cost["total"] == 10 + 3 + 2 * (1 + 3)
+    assert cost["fields"]["query.expensive.friends"] == 3
+    assert cost["fields"]["query.users.friends"] == 3
+    assert cost["fields"]["query.users.friends.name"] == 0
+
+
+def test_fragments_spread_repeatedly():
+    fragments = " ".join(
+        f"fragment F{index} on User {{ friends {{ ...F{index + 1} ...F{index + 1} }} }}"
+        for index in range(30)
+    )
+    cost = estimate(f"{{ expensive {{ ...F0 }} }} {fragments} fragment F30 on User {{ name }}")
+
+    assert cost["total"] == 10 + sum(2**index for index in range(30))
+
+
+def test_max_cost():
+    compiler = QueryCompiler(SCHEMA, max_cost=5)
+
+    assert compiler.validation_errors(compiler.parse("{ users(first: 3) { name } }")) == []
+
+    errors = compiler.validation_errors(compiler.parse("{ users(first: 10) { name } }"))
+    assert codes(errors) == ["MaxCostExceeded"]
+    assert errors[0].message == "Query cost of 10 exceeds the maximum allowed cost of 5"
+
+
+def test_max_cost_uses_variable_defaults():
+    compiler = QueryCompiler(SCHEMA, max_cost=5)
+
+    assert compiler.validate(compiler.parse("query($n: Int = 5) { users(first: $n) { name } }"))
+    assert not compiler.validate(compiler.parse("query($n: Int = 6) { users(first: $n) { name } }"))
+
+
+def test_max_cost_uses_assumed_size_for_variables_without_default():
+    compiler = QueryCompiler(SCHEMA, max_cost=5)
+
+    assert compiler.validate(compiler.parse("query($n: Int) { featured(first: $n) { name } }"))
+    assert not compiler.validate(
+        compiler.parse("query($n: Int) { featured(first: $n) { friends { name } } }")
+    )
+
+
+def test_max_cost_treats_unsized_variable_lists_as_unbounded():
+    compiler = QueryCompiler(SCHEMA, max_cost=100)
+    document = compiler.parse("query Users($n: Int) { users(first: $n) { name } }")
+
+    errors = compiler.validation_errors(document)
+
+    assert codes(errors) == ["MaxCostExceeded"]
+    assert errors[0].message == (
+        "Query cost exceeds the maximum allowed cost of 100, as list sizes given by variables "
+        "without a default value are unbounded"
+    )
+
+
+def test_max_cost_allows_variable_sized_lists_of_free_items():
+    compiler = QueryCompiler(SCHEMA, max_cost=1)
+    document = compiler.parse("query Tags($n: Int) { tags(first: $n) }")
+
+    assert compiler.validation_errors(document) == []
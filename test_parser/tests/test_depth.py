@@ -0,0 +1,80 @@
+from conftest import SCHEMA, codes
+from rustberry import QueryCompiler
+
+
+def test_within_max_depth():
+    compiler = QueryCompiler(SCHEMA, max_depth=1)
+    document = compiler.parse("{ user { friends { name } } }")
+
+    assert compiler.validation_errors(document) == []
+
+
+def test_exceeding_max_depth():
+    compiler = QueryCompiler(SCHEMA, max_depth=1)
+    document = compiler.parse("query Deep { user { friends { friends { name } } } }")
+
+    errors = compiler.validation_errors(document)
+
+    assert codes(errors) == ["MaxDepthExceeded"]
+    assert errors[0].message == "'Deep' exceeds maximum operation depth of 1"
+
+
+def test_reports_once_per_operation_at_first_field():
+    compiler = QueryCompiler(SCHEMA, max_depth=1)
+    fields = " ".join(f"name{index}: name" for index in range(50))
+    document = compiler.parse(f"query Wide {{ user {{ friends {{ {fields} }} }} }}")
+
+    errors = compiler.validation_errors(document)
+
+    assert codes(errors) == ["MaxDepthExceeded"]
+    assert errors[0].locations[0].column == len("query Wide { user { friends { ") + 1
+
+
+def test_reports_each_operation():
+    compiler = QueryCompiler(SCHEMA, max_depth=0)
+    document = compiler.parse(
+        "query A { user { name } } query B { user { friends { name } } }"
+    )
+
+    errors = compiler.validation_errors(document)
+
+    assert [error.message for error in errors] == [
+        "'A' exceeds maximum operation depth of 0",
+        "'B' exceeds maximum operation depth of 0",
+    ]
+
+
+def test_follows_fragments():
+    compiler = QueryCompiler(SCHEMA, max_depth=1)
+    document = compiler.parse(
+        "{ user { ...Friends } } fragment Friends on User { friends { friends { name } } }"
+    )
+
+    assert codes(compiler.validation_errors(document)) == ["MaxDepthExceeded"]
+
+
+def test_ignores_introspection_fields():
+    compiler = QueryCompiler(SCHEMA, max_depth=0)
+    document = compiler.parse("{ __schema { types { fields { name } } } }")
+
+    assert compiler.validation_errors(document) == []
+
+
+def test_max_depth_overridden_per_call():
+    compiler = QueryCompiler(SCHEMA)
+    document = compiler.parse("{ user { friends { name } } }")
+
+    assert compiler.validate(document)
+    assert not compiler.validate(document, max_depth=1)
+
+
+def test_fragments_spread_repeatedly_are_walked_once():
+    compiler = QueryCompiler(SCHEMA, max_depth=40)
+    fragments = " ".join(
+        f"fragment F{index} on User {{ friends {{ ...F{index + 1} ...F{index + 1} }} }}"
+        for index in range(30)
+    )
+    document = compiler.parse(f"{{ user {{ ...F0 }} }} {fragments} fragment F30 on User {{ name }}")
+
+    assert compiler.validation_errors(document) == []
+    assert codes(compiler.validation_errors(document, max_depth=20)) == ["MaxDepthExceeded"]
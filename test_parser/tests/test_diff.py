@@ -0,0 +1,191 @@
+import pytest
+
+from rustberry import QueryCompiler, SchemaChange, diff_schemas
+
+
+def changes(old, new):
+    return [
+        (change.criticality, change.type, change.description) for change in diff_schemas(old, new)
+    ]
+
+
+@pytest.mark.parametrize(
+    "old, new, expected",
+    [
+        pytest.param(
+            "type Query { a: String b: String }",
+            "type Query { a: String }",
+            ("BREAKING", "FIELD_REMOVED", "Query.b was removed."),
+            id="field removed",
+        ),
+        pytest.param(
+            "type Query { a: String }",
+            "type Query { a: String b: String }",
+            ("SAFE", "FIELD_ADDED", "Query.b was added."),
+            id="field added",
+        ),
+        pytest.param(
+            "type Query { a: String }",
+            "type Query { a: Int }",
+            ("BREAKING", "FIELD_CHANGED_KIND", "Query.a changed type from String to Int."),
+            id="field type changed",
+        ),
+        pytest.param(
+            "type Query { a: String! }",
+            "type Query { a: String }",
+            ("BREAKING", "FIELD_CHANGED_KIND", "Query.a changed type from String! to String."),
+            id="field type made nullable",
+        ),
+        pytest.param(
+            "type Query { a: String }",
+            "type Query { a: String! }",
+            ("SAFE", "FIELD_TYPE_CHANGED", "Query.a changed type from String to String!."),
+            id="field type made non-null",
+        ),
+        pytest.param(
+            "type Query { f(a: Int): String }",
+            "type Query { f(a: Int!): String }",
+            ("BREAKING", "ARG_CHANGED_KIND", "Query.f arg a has changed type from Int to Int!."),
+            id="arg type narrowed",
+        ),
+        pytest.param(
+            "type Query { f(a: Int!): String }",
+            "type Query { f(a: Int): String }",
+            ("SAFE", "ARG_TYPE_CHANGED", "Query.f arg a has changed type from Int! to Int."),
+            id="arg type widened",
+        ),
+        pytest.param(
+            "type Query { f(a: Int): String }",
+            "type Query { f: String }",
+            ("BREAKING", "ARG_REMOVED", "Query.f arg a was removed."),
+            id="arg removed",
+        ),
+        pytest.param(
+            "type Query { f: String }",
+            "type Query { f(a: Int!): String }",
+            ("BREAKING", "REQUIRED_ARG_ADDED", "A required arg a on Query.f was added."),
+            id="required arg added",
+        ),
+        pytest.param(
+            "type Query { f: String }",
+            "type Query { f(a: Int): String }",
+            ("DANGEROUS", "OPTIONAL_ARG_ADDED", "An optional arg a on Query.f was added."),
+            id="optional arg added",
+        ),
+        pytest.param(
+            "type Query { f(a: Int = 1): String }",
+            "type Query { f(a: Int = 2): String }",
+            (
+                "DANGEROUS",
+                "ARG_DEFAULT_VALUE_CHANGE",
+                "Query.f arg a has changed defaultValue from 1 to 2.",
+            ),
+            id="default changed",
+        ),
+        pytest.param(
+            "type Query { f(a: Int = 1): String }",
+            "type Query { f(a: Int): String }",
+            ("DANGEROUS", "ARG_DEFAULT_VALUE_CHANGE", "Query.f arg a defaultValue was removed."),
+            id="default removed",
+        ),
+        pytest.param(
+            "type Query { a: E } enum E { A B }",
+            "type Query { a: E } enum E { A }",
+            ("BREAKING", "VALUE_REMOVED_FROM_ENUM", "B was removed from enum type E."),
+            id="enum value removed",
+        ),
+        pytest.param(
+            "type Query { a: E } enum E { A }",
+            "type Query { a: E } enum E { A B }",
+            ("DANGEROUS", "VALUE_ADDED_TO_ENUM", "B was added to enum type E."),
+            id="enum value added",
+        ),
+        pytest.param(
+            "type Query { a: T } interface N { id: ID } type T implements N { id: ID }",
+            "type Query { a: T } interface N { id: ID } type T { id: ID }",
+            ("BREAKING", "IMPLEMENTED_INTERFACE_REMOVED", "T no longer implements interface N."),
+            id="interface removed",
+        ),
+        pytest.param(
+            "type Query { a: T } interface N { id: ID } type T { id: ID }",
+            "type Query { a: T } interface N { id: ID } type T implements N { id: ID }",
+            (
+                "DANGEROUS",
+                "IMPLEMENTED_INTERFACE_ADDED",
+                "N added to interfaces implemented by T.",
+            ),
+            id="interface added",
+        ),
+        pytest.param(
+            "type Query { a: String } type T { id: ID }",
+            "type Query { a: String } input T { id: ID }",
+            ("BREAKING", "TYPE_CHANGED_KIND", "T changed from an Object type to an Input type."),
+            id="type kind changed",
+        ),
+        pytest.param(
+            "type Query { a: String } type T { id: ID }",
+            "type Query { a: String }",
+            ("BREAKING", "TYPE_REMOVED", "T was removed."),
+            id="type removed",
+        ),
+        pytest.param(
+            "type Query { a: String }",
+            "type Query { a: String } type T { id: ID }",
+            ("SAFE", "TYPE_ADDED", "T was added."),
+            id="type added",
+        ),
+        pytest.param(
+            "type Query { a: U } union U = A type A { id: ID } type B { id: ID }",
+            "type Query { a: U } union U = A | B type A { id: ID } type B { id: ID }",
+            ("DANGEROUS", "TYPE_ADDED_TO_UNION", "B was added to union type U."),
+            id="union member added",
+        ),
+        pytest.param(
+            "type Query { a: U } union U = A | B type A { id: ID } type B { id: ID }",
+            "type Query { a: U } union U = A type A { id: ID } type B { id: ID }",
+            ("BREAKING", "TYPE_REMOVED_FROM_UNION", "B was removed from union type U."),
+            id="union member removed",
+        ),
+        pytest.param(
+            "type Query { f(i: I): String } input I { a: Int }",
+            "type Query { f(i: I): String } input I { a: Int b: Int! }",
+            (
+                "BREAKING",
+                "REQUIRED_INPUT_FIELD_ADDED",
+                "A required field b on input type I was added.",
+            ),
+            id="required input field added",
+        ),
+        pytest.param(
+            "type Query { a: String } directive @d on FIELD",
+            "type Query { a: String }",
+            ("BREAKING", "DIRECTIVE_REMOVED", "d was removed."),
+            id="directive removed",
+        ),
+    ],
+)
+def test_change(old, new, expected):
+    assert changes(old, new) == [expected]
+
+
+def test_identical_schemas():
+    schema = "type Query { f(a: Int = 1): String } enum E { A }"
+
+    assert changes(schema, schema) == []
+
+
+def test_default_object_field_order_is_ignored():
+    old = "type Query { f(i: I = {a: 1, b: [{a: 2, b: []}]}): String } input I { a: Int b: [I] }"
+    new = "type Query { f(i: I = {b: [{b: [], a: 2}], a: 1}): String } input I { a: Int b: [I] }"
+
+    assert changes(old, new) == []
+
+
+def test_compiler_diff():
+    old = QueryCompiler("type Query { a: String b: String }")
+    new = QueryCompiler("type Query { a: String }")
+
+    [change] = old.diff(new)
+
+    assert isinstance(change, SchemaChange)
+    assert (change.criticality, change.type) == ("BREAKING", "FIELD_REMOVED")
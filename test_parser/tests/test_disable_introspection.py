@@ -0,0 +1,38 @@
+from conftest import SCHEMA, codes
+from rustberry import QueryCompiler
+
+
+def test_introspection_allowed_by_default():
+    compiler = QueryCompiler(SCHEMA)
+
+    assert compiler.validate(compiler.parse("{ __schema { queryType { name } } }"))
+
+
+def test_disable_introspection():
+    compiler = QueryCompiler(SCHEMA, disable_introspection=True)
+
+    errors = compiler.validation_errors(
+        compiler.parse('{ __schema { queryType { name } } __type(name: "Query") { name } }')
+    )
+
+    assert codes(errors) == ["IntrospectionDisabled", "IntrospectionDisabled"]
+    assert errors[0].message == (
+        "GraphQL introspection has been disabled, but the requested query contained "
+        "the field '__schema'."
+    )
+
+
+def test_disable_introspection_checks_fragments():
+    compiler = QueryCompiler(SCHEMA, disable_introspection=True)
+
+    errors = compiler.validation_errors(
+        compiler.parse("{ ...Schema } fragment Schema on Query { __schema { types { name } } }")
+    )
+
+    assert codes(errors) == ["IntrospectionDisabled"]
+
+
+def test_typename_allowed():
+    compiler = QueryCompiler(SCHEMA, disable_introspection=True)
+
+    assert compiler.validate(compiler.parse("{ __typename name }"))
@@ -0,0 +1,139 @@
+from graphql import build_schema, get_introspection_query, graphql_sync
+
+from rustberry import QueryCompiler
+
+SCHEMA = '''
+"""The root query"""
+type Query {
+  user(id: ID!): User
+  search(term: String = "all", limit: Int): [Result!]!
+}
+
+interface Node {
+  id: ID!
+}
+
+"""A user"""
+type User implements Node {
+  id: ID!
+  name: String @deprecated(reason: "Use fullName")
+  fullName: String
+  role: Role
+}
+
+type Post implements Node {
+  id: ID!
+  title: String
+}
+
+union Result = User | Post
+
+enum Role {
+  ADMIN
+  MEMBER @deprecated
+}
+
+input Filter {
+  role: Role = MEMBER
+  name: String
+}
+'''
+
+
+def execute(query, **kwargs):
+    compiler = QueryCompiler(SCHEMA)
+    return compiler.execute_introspection(compiler.parse(query), **kwargs)
+
+
+BUILT_IN_SCALARS = {"String", "Int", "Float", "Boolean", "ID"}
+
+
+def user_types(result):
+    """The types defined by the schema, by name, leaving out the built-in ones whose
+    descriptions differ between implementations."""
+    types = result["data"]["__schema"]["types"]
+    return sorted(
+        (
+            type_
+            for type_ in types
+            if not type_["name"].startswith("__") and type_["name"] not in BUILT_IN_SCALARS
+        ),
+        key=lambda type_: type_["name"],
+    )
+
+
+def test_typename():
+    assert execute("{ __typename }") == {"data": {"__typename": "Query"}}
+
+
+def test_type():
+    result = execute(
+        '{ __type(name: "User") { kind name description interfaces { name } '
+        "fields(includeDeprecated: true) { name isDeprecated deprecationReason } } }"
+    )
+
+    assert result == {
+        "data": {
+            "__type": {
+                "kind": "OBJECT",
+                "name": "User",
+                "description": "A user",
+                "interfaces": [{"name": "Node"}],
+                "fields": [
+                    {"name": "id", "isDeprecated": False, "deprecationReason": None},
+                    {"name": "name", "isDeprecated": True, "deprecationReason": "Use fullName"},
+                    {"name": "fullName", "isDeprecated": False, "deprecationReason": None},
+                    {"name": "role", "isDeprecated": False, "deprecationReason": None},
+                ],
+            }
+        }
+    }
+
+
+def test_deprecated_fields_hidden_by_default():
+    result = execute('{ __type(name: "User") { fields { name } } }')
+
+    names = [field["name"] for field in result["data"]["__type"]["fields"]]
+    assert names == ["id", "fullName", "role"]
+
+
+def test_unknown_type_is_null():
+    assert execute('{ __type(name: "Unknown") { name } }') == {"data": {"__type": None}}
+
+
+def test_variables_and_aliases():
+    result = execute(
+        "query Q($name: String!) { t: __type(name: $name) { name kind } }",
+        variables={"name": "Role"},
+    )
+
+    assert result == {"data": {"t": {"name": "Role", "kind": "ENUM"}}}
+
+
+def test_invalid_variables_are_reported():
+    result = execute("query Q($name: String!) { __type(name: $name) { name } }")
+
+    assert result["data"] is None
+    assert result["errors"][0]["message"] == (
+        "Variable '$name' of required type 'String!' was not provided."
+    )
+
+
+def test_invalid_document_is_reported():
+    result = execute("{ __schema { unknown } }")
+
+    assert result["data"] is None
+    assert len(result["errors"]) == 1
+
+
+def test_matches_graphql_core():
+    query = get_introspection_query(descriptions=True)
+    expected = graphql_sync(build_schema(SCHEMA), query).data
+
+    result = execute(query)
+
+    assert "errors" not in result
+    schema = result["data"]["__schema"]
+    assert schema["queryType"] == expected["__schema"]["queryType"]
+    assert schema["mutationType"] is None
+    assert user_types(result) == user_types({"data": expected})
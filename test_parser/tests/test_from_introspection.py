@@ -0,0 +1,144 @@
+import json
+
+import pytest
+from graphql import build_schema, get_introspection_query, graphql_sync
+
+from rustberry import QueryCompiler
+
+SCHEMA = '''
+"""The root query"""
+type Query {
+  user(id: ID!): User
+  search(term: String = "all", filter: Filter = {role: MEMBER, tags: ["a"]}): [Result!]!
+}
+
+interface Node {
+  id: ID!
+}
+
+"""A user"""
+type User implements Node {
+  id: ID!
+  name: String @deprecated(reason: "Use fullName")
+  fullName: String
+  role: Role
+}
+
+type Post implements Node {
+  id: ID!
+  title: String
+}
+
+union Result = User | Post
+
+enum Role {
+  ADMIN
+  MEMBER @deprecated(reason: "No longer supported")
+}
+
+input Filter {
+  role: Role = MEMBER
+  tags: [String!]
+}
+
+scalar DateTime
+
+directive @auth(requires: Role = ADMIN) repeatable on FIELD_DEFINITION | OBJECT
+'''
+
+
+def introspection():
+    query = get_introspection_query(descriptions=True, directive_is_repeatable=True)
+    return graphql_sync(build_schema(SCHEMA), query).data
+
+
+def find(items, name):
+    return next(item for item in items if item["name"] == name)
+
+
+def test_round_trip():
+    compiler = QueryCompiler.from_introspection(introspection())
+
+    assert compiler.print_schema(sorted=True) == QueryCompiler(SCHEMA).print_schema(sorted=True)
+
+
+def test_accepts_json_with_data():
+    compiler = QueryCompiler.from_introspection(json.dumps({"data": introspection()}))
+
+    assert compiler.validate(compiler.parse("{ user(id: 1) { fullName } }"))
+
+
+def test_passes_keyword_arguments():
+    compiler = QueryCompiler.from_introspection(introspection(), max_depth=0)
+
+    assert not compiler.validate(compiler.parse("{ user(id: 1) { fullName } }"))
+
+
+def test_rejects_incomplete_result():
+    with pytest.raises(TypeError, match="Invalid or incomplete introspection result"):
+        QueryCompiler.from_introspection({"data": {}})
+
+
+@pytest.mark.parametrize(
+    "name",
+    ["Foo { x: Int } type Evil", "1User", "", "User!", "User @deprecated"],
+)
+def test_rejects_invalid_type_names(name):
+    result = introspection()
+    find(result["__schema"]["types"], "Post")["name"] = name
+
+    with pytest.raises(TypeError, match="Names must match"):
+        QueryCompiler.from_introspection(result)
+
+
+def test_rejects_invalid_field_names():
+    result = introspection()
+    user = find(result["__schema"]["types"], "User")
+    find(user["fields"], "fullName")["name"] = "fullName: String } type Evil { x"
+
+    with pytest.raises(TypeError, match="Names must match"):
+        QueryCompiler.from_introspection(result)
+
+
+def test_rejects_invalid_enum_values():
+    result = introspection()
+    find(result["__schema"]["types"], "Role")["enumValues"][0]["name"] = "ADMIN } type Evil {"
+
+    with pytest.raises(TypeError, match="Names must match"):
+        QueryCompiler.from_introspection(result)
+
+
+@pytest.mark.parametrize(
+    "default_value",
+    [
+        '"all") evil: Int, (x: Int = 1',
+        '"all" # comment',
+        "1 2",
+        "{role: MEMBER",
+        "[1, 2",
+        "{role MEMBER}",
+        "$variable",
+        "",
+    ],
+)
+def test_rejects_invalid_default_values(default_value):
+    result = introspection()
+    query = find(result["__schema"]["types"], "Query")
+    find(find(query["fields"], "search")["args"], "term")["defaultValue"] = default_value
+
+    with pytest.raises(TypeError, match="Invalid default value"):
+        QueryCompiler.from_introspection(result)
+
+
+def test_accepts_nested_default_values():
+    result = introspection()
+    query = find(result["__schema"]["types"], "Query")
+    filter_argument = find(find(query["fields"], "search")["args"], "filter")
+    filter_argument["defaultValue"] = '{role: ADMIN, tags: ["x", "y"]}'
+
+    compiler = QueryCompiler.from_introspection(result)
+
+    expected = QueryCompiler(
+        SCHEMA.replace('{role: MEMBER, tags: ["a"]}', '{role: ADMIN, tags: ["x", "y"]}')
+    )
+    assert compiler.print_schema(sorted=True) == expected.print_schema(sorted=True)
@@ -0,0 +1,36 @@
+import pytest
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  user(id: ID): User
+}
+
+type User {
+  name: String
+}
+"""
+
+QUERY = "{ user(id: 1) { ... on User { name } } }"
+
+
+@pytest.mark.parametrize("lazy", [False, True])
+def test_schema_names_are_shared_between_documents(lazy):
+    compiler = QueryCompiler(SCHEMA)
+    first = compiler.gql_core_ast_mirror(compiler.parse(QUERY), lazy=lazy)
+    second = compiler.gql_core_ast_mirror(compiler.parse(QUERY), lazy=lazy)
+
+    def names(ast):
+        user = ast.definitions[0].selection_set.selections[0]
+        inline_fragment = user.selection_set.selections[0]
+        return [
+            user.name.value,
+            user.arguments[0].name.value,
+            inline_fragment.type_condition.name.value,
+            inline_fragment.selection_set.selections[0].name.value,
+        ]
+
+    assert names(first) == ["user", "id", "User", "name"]
+    for first_name, second_name in zip(names(first), names(second)):
+        assert first_name is second_name
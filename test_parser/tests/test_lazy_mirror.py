@@ -0,0 +1,50 @@
+from graphql.language.ast import (
+    DocumentNode,
+    FieldNode,
+    OperationDefinitionNode,
+    SelectionSetNode,
+)
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  user: User
+}
+
+type User {
+  name: String
+}
+"""
+
+
+def test_lazy_nodes_pass_as_graphql_core_nodes():
+    compiler = QueryCompiler(SCHEMA)
+    ast = compiler.gql_core_ast_mirror(compiler.parse("{ user { name } }"), lazy=True)
+
+    operation = ast.definitions[0]
+    selection_set = operation.selection_set
+    field = selection_set.selections[0]
+
+    assert isinstance(ast, DocumentNode)
+    assert isinstance(operation, OperationDefinitionNode)
+    assert isinstance(selection_set, SelectionSetNode)
+    assert isinstance(field, FieldNode)
+    assert field.name.value == "user"
+    assert field.selection_set.selections[0].name.value == "name"
+
+
+def test_lazy_children_are_kept():
+    compiler = QueryCompiler(SCHEMA)
+    ast = compiler.gql_core_ast_mirror(compiler.parse("{ user { name } }"), lazy=True)
+
+    assert ast.definitions[0] is ast.definitions[0]
+
+
+def test_lazy_ast_outlives_cache_entry():
+    compiler = QueryCompiler(SCHEMA, cache_size=1)
+    ast = compiler.gql_core_ast_mirror(compiler.parse("{ user { name } }"), lazy=True)
+    compiler.parse("{ user { __typename } }")
+    compiler.cache_clear()
+
+    assert ast.definitions[0].selection_set.selections[0].name.value == "user"
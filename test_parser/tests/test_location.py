@@ -0,0 +1,78 @@
+from graphql.language import TokenKind
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  user: User
+}
+
+type User {
+  name: String
+}
+"""
+
+QUERY = "query Q {\n  user { name }\n}\n# trailing comment\n"
+
+
+def mirror(query=QUERY, **kwargs):
+    compiler = QueryCompiler(SCHEMA)
+    return compiler.gql_core_ast_mirror(compiler.parse(query), **kwargs)
+
+
+def test_loc_offsets():
+    ast = mirror()
+    operation = ast.definitions[0]
+    field = operation.selection_set.selections[0]
+
+    assert (ast.loc.start, ast.loc.end) == (0, len(QUERY))
+    assert QUERY[field.loc.start : field.loc.end] == "user { name }"
+    assert QUERY[field.name.loc.start : field.name.loc.end] == "user"
+
+
+def test_no_location():
+    ast = mirror(no_location=True)
+    operation = ast.definitions[0]
+
+    assert ast.loc is None
+    assert operation.loc is None
+    assert operation.selection_set.selections[0].loc is None
+
+
+def test_start_and_end_tokens():
+    field = mirror().definitions[0].selection_set.selections[0]
+
+    start, end = field.loc.start_token, field.loc.end_token
+    assert (start.kind, start.value) == (TokenKind.NAME, "user")
+    assert (start.line, start.column) == (2, 3)
+    assert (start.start, start.end) == (field.loc.start, field.loc.start + 4)
+    assert (end.kind, end.value) == (TokenKind.BRACE_R, None)
+    assert (end.line, end.column) == (2, 15)
+
+
+def test_end_token_skips_eof_and_comments():
+    ast = mirror()
+
+    assert ast.loc.start_token.kind == TokenKind.NAME
+    assert ast.loc.start_token.value == "query"
+    assert ast.loc.end_token.kind == TokenKind.BRACE_R
+    assert (ast.loc.end_token.line, ast.loc.end_token.column) == (3, 1)
+
+
+def test_string_token_value():
+    compiler = QueryCompiler("type Query { user(name: String): String }")
+    ast = compiler.gql_core_ast_mirror(compiler.parse('{ user(name: "ada") }'))
+    value = ast.definitions[0].selection_set.selections[0].arguments[0].value
+
+    assert value.loc.start_token.kind == TokenKind.STRING
+    assert value.loc.start_token.value == "ada"
+
+
+def test_loc_source():
+    compiler = QueryCompiler(SCHEMA)
+    ast = compiler.gql_core_ast_mirror(compiler.parse(QUERY, source_name="q.graphql"))
+    field = ast.definitions[0].selection_set.selections[0]
+
+    assert ast.loc.source.body == QUERY
+    assert ast.loc.source.name == "q.graphql"
+    assert field.loc.source is ast.loc.source
@@ -0,0 +1,66 @@
+import pytest
+
+from rustberry import OperationError, QueryCompiler
+
+SCHEMA = """
+type Query {
+  user(id: ID!): String
+}
+
+type Mutation {
+  rename(name: String!): String
+}
+"""
+
+DOCUMENT = """
+query Q($id: ID!) { user(id: $id) }
+mutation M($name: String!) { rename(name: $name) }
+fragment F on Query { user(id: 1) }
+"""
+
+
+def operation_error(call, *args):
+    with pytest.raises(OperationError) as exc_info:
+        call(*args)
+    return [error.message for error in exc_info.value.errors]
+
+
+def test_inspect_named_operations():
+    document = QueryCompiler(SCHEMA).parse(DOCUMENT)
+
+    assert document.operation_names() == ["Q", "M"]
+    assert document.fragment_names() == ["F"]
+    assert document.operation_type("Q") == "query"
+    assert document.operation_type("M") == "mutation"
+    assert document.variable_definitions("M") == {"name": "String!"}
+    assert "rename(name: $name)" in document.get_operation("M")
+
+
+def test_single_operation_needs_no_name():
+    document = QueryCompiler(SCHEMA).parse("{ user(id: 1) }")
+
+    assert document.operation_names() == [None]
+    assert document.operation_type() == "query"
+    assert document.variable_definitions() == {}
+
+
+@pytest.mark.parametrize("method", ["get_operation", "operation_type", "variable_definitions"])
+def test_operation_must_be_determined(method):
+    compiler = QueryCompiler(SCHEMA)
+    document = compiler.parse(DOCUMENT)
+    call = getattr(document, method)
+
+    assert operation_error(call) == [
+        "Must provide operation name if query contains multiple operations."
+    ]
+    assert operation_error(call, "X") == ["Unknown operation named 'X'."]
+    assert operation_error(call) == operation_error(compiler.get_operation, document)
+    assert operation_error(call, "X") == operation_error(compiler.get_operation, document, "X")
+
+
+def test_document_without_operations():
+    compiler = QueryCompiler(SCHEMA)
+    document = compiler.parse("fragment F on Query { user(id: 1) }")
+
+    assert document.operation_names() == []
+    assert operation_error(document.operation_type) == ["Must provide an operation."]
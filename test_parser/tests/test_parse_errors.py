@@ -0,0 +1,44 @@
+import pytest
+
+from rustberry import GraphQLError, ParseError, QueryCompiler
+
+SCHEMA = """
+type Query {
+  user: User
+}
+
+type User {
+  name: String
+}
+"""
+
+
+def parse_errors(query):
+    with pytest.raises(ParseError, match="Document could not be parsed") as exc_info:
+        QueryCompiler(SCHEMA).parse(query)
+    return exc_info.value.errors
+
+
+def test_syntax_error():
+    errors = parse_errors("{\n  user {\n    name\n  }\n  )\n}")
+
+    assert errors
+    assert all(isinstance(error, GraphQLError) for error in errors)
+    assert (errors[0].locations[0].line, errors[0].locations[0].column) == (5, 3)
+    assert errors[0].path == []
+    assert errors[0].formatted["message"] == errors[0].message
+    assert errors[0].formatted["locations"] == [{"line": 5, "column": 3}]
+
+
+def test_build_error():
+    errors = parse_errors("query A { user { name } } query A { user { name } }")
+
+    assert len(errors) == 1
+    assert "A" in errors[0].message
+    assert errors[0].locations
+
+
+def test_valid_document_parses():
+    document = QueryCompiler(SCHEMA).parse("{ user { name } }")
+
+    assert document.operation_names() == [None]
@@ -0,0 +1,82 @@
+import pytest
+
+from conftest import SCHEMA, codes
+from rustberry import ParseError, QueryCompiler
+
+
+def parse_error_codes(compiler, query):
+    with pytest.raises(ParseError) as exc_info:
+        compiler.parse(query)
+    return codes(exc_info.value.errors)
+
+
+def test_limits_disabled_by_default():
+    compiler = QueryCompiler(SCHEMA)
+    aliases = " ".join(f"a{index}: name" for index in range(100))
+
+    compiler.parse(f"{{ user {{ {aliases} }} }}")
+
+
+def test_max_tokens():
+    compiler = QueryCompiler(SCHEMA, max_tokens=50)
+    aliases = " ".join(f"a{index}: name" for index in range(100))
+
+    compiler.parse("{ user { name } }")
+    assert "MaxTokensExceeded" in parse_error_codes(compiler, f"{{ user {{ {aliases} }} }}")
+
+
+def test_max_recursion():
+    compiler = QueryCompiler(SCHEMA, max_recursion=5)
+    nested = "{ friends " * 20 + "{ name }" + " }" * 20
+
+    compiler.parse("{ user { name } }")
+    assert "MaxRecursionExceeded" in parse_error_codes(compiler, f"{{ user {nested} }}")
+
+
+def test_max_aliases():
+    compiler = QueryCompiler(SCHEMA, max_aliases=2)
+
+    compiler.parse("{ a: user { b: name } }")
+    assert parse_error_codes(
+        compiler, "{ a: user { b: name c: name } } fragment F on User { d: name }"
+    ) == ["MaxAliasesExceeded"]
+
+
+def test_max_aliases_message():
+    compiler = QueryCompiler(SCHEMA, max_aliases=1)
+
+    with pytest.raises(ParseError) as exc_info:
+        compiler.parse("{ a: user { b: name c: name } }")
+
+    [error] = exc_info.value.errors
+    assert error.message == "Document contains 3 aliases, exceeding the maximum of 1"
+    assert error.locations[0].column == len("{ a: user { ") + 1
+
+
+def test_max_root_fields_counts_fragments():
+    compiler = QueryCompiler(SCHEMA, max_root_fields=2)
+
+    compiler.parse("{ user { name } users { name } }")
+    assert parse_error_codes(
+        compiler,
+        "query Q { user { name } ...Root } "
+        "fragment Root on Query { users { name } user { name } }",
+    ) == ["MaxRootFieldsExceeded"]
+
+
+def test_max_root_fields_with_fragment_cycle():
+    compiler = QueryCompiler(SCHEMA, max_root_fields=2)
+
+    assert parse_error_codes(
+        compiler,
+        "{ ...A } fragment A on Query { user { name } users { name } x: user { name } ...A }",
+    ) == ["MaxRootFieldsExceeded"]
+
+
+def test_max_directives_per_field():
+    compiler = QueryCompiler(SCHEMA, max_directives_per_field=1)
+
+    compiler.parse("{ user @include(if: true) { name } }")
+    assert parse_error_codes(
+        compiler, "{ user @include(if: true) @skip(if: false) { name } }"
+    ) == ["MaxDirectivesExceeded"]
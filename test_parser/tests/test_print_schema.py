@@ -0,0 +1,80 @@
+import pytest
+from graphql import build_schema, print_schema
+
+from rustberry import QueryCompiler
+
+SCHEMA = '''"""The root query"""
+type Query {
+  """Finds a user"""
+  user(id: ID!): User
+  search(term: String = "all", limit: Int): [Result!]!
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String @deprecated(reason: "Use fullName")
+}
+
+type Post implements Node {
+  id: ID!
+}
+
+union Result = User | Post
+
+enum Role {
+  MEMBER
+  ADMIN
+}
+'''
+
+
+def test_prints_schema():
+    assert QueryCompiler(SCHEMA).print_schema() == SCHEMA
+
+
+def test_merges_extensions():
+    compiler = QueryCompiler(
+        {"schema.graphql": SCHEMA, "ext.graphql": "extend type Post { title: String }"}
+    )
+
+    assert "type Post implements Node {\n  id: ID!\n  title: String\n}" in compiler.print_schema()
+
+
+def test_sorted():
+    printed = QueryCompiler(SCHEMA).print_schema(sorted=True)
+
+    assert "enum Role {\n  ADMIN\n  MEMBER\n}" in printed
+    assert printed.index("interface Node") < printed.index("type Query") < printed.index("enum Role")
+    assert printed == QueryCompiler(printed).print_schema(sorted=True)
+
+
+def test_include_builtins():
+    printed = QueryCompiler(SCHEMA).print_schema(include_builtins=True)
+
+    assert "scalar String" in printed
+    assert "directive @skip" in printed
+    assert "type __Schema" in printed
+
+
+@pytest.mark.parametrize(
+    "description",
+    ['say "hi"', "ends in a backslash \\", 'contains """ quotes', 'ends in """', "two\nlines"],
+)
+def test_descriptions_round_trip(description):
+    schema = build_schema("type Query { name: String }")
+    schema.query_type.fields["name"].description = description
+    printed = QueryCompiler(print_schema(schema)).print_schema()
+
+    reparsed = build_schema(printed)
+    assert reparsed.query_type.fields["name"].description == description
+    assert QueryCompiler(printed).print_schema() == printed
+
+
+def test_description_ending_in_quote_closes_on_next_line():
+    compiler = QueryCompiler('type Query {\n  "say \\"hi\\""\n  name: String\n}')
+
+    assert compiler.print_schema() == 'type Query {\n  """\n  say "hi"\n  """\n  name: String\n}\n'
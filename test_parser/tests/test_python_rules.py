@@ -0,0 +1,120 @@
+import pytest
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  user: User
+}
+
+type User {
+  name: String
+  old: String @deprecated(reason: "Use name")
+}
+"""
+
+QUERY = """query Q {
+  user {
+    name
+    ...F
+    ... on User { old }
+  }
+}
+fragment F on User { name }
+"""
+
+
+class Recorder:
+    def __init__(self):
+        self.events = []
+
+    def __getattr__(self, method):
+        if not method.startswith(("enter_", "leave_")):
+            raise AttributeError(method)
+        return lambda node, context: self.events.append((method, node.name or node.type))
+
+
+class ReportDeprecated:
+    def enter_field(self, node, context):
+        if node.is_deprecated:
+            context.report_error(
+                f"{node.parent_type}.{node.name} is deprecated. {node.deprecation_reason}",
+                node,
+                code="DeprecatedField",
+            )
+
+    def leave_operation_definition(self, node, context):
+        context.report_error(f"Visited {node.operation} {node.name}")
+
+
+def validation_errors(rules, query=QUERY):
+    compiler = QueryCompiler(SCHEMA)
+    return compiler.validation_errors(compiler.parse(query), rules=rules)
+
+
+def test_enter_and_leave_order():
+    recorder = Recorder()
+
+    assert validation_errors([recorder]) == []
+    assert recorder.events == [
+        ("enter_operation_definition", "Q"),
+        ("enter_field", "user"),
+        ("enter_field", "name"),
+        ("leave_field", "name"),
+        ("enter_fragment_spread", "F"),
+        ("leave_fragment_spread", "F"),
+        ("enter_inline_fragment", "User"),
+        ("enter_field", "old"),
+        ("leave_field", "old"),
+        ("leave_inline_fragment", "User"),
+        ("leave_field", "user"),
+        ("leave_operation_definition", "Q"),
+        ("enter_fragment_definition", "F"),
+        ("enter_field", "name"),
+        ("leave_field", "name"),
+        ("leave_fragment_definition", "F"),
+    ]
+
+
+def test_fragment_definition_nodes():
+    nodes = []
+
+    class Rule:
+        def enter_fragment_definition(self, node, context):
+            nodes.append(node)
+
+    validation_errors([Rule()])
+
+    [node] = nodes
+    assert (node.kind, node.name, node.type) == ("fragment_definition", "F", "User")
+    assert node.parent_type is None
+
+
+def test_report_error_locations_and_codes():
+    errors = validation_errors([ReportDeprecated()])
+
+    assert [error.message for error in errors] == [
+        "User.old is deprecated. Use name",
+        "Visited query Q",
+    ]
+    assert errors[0].extensions == {"code": "DeprecatedField"}
+    assert [(location.line, location.column) for location in errors[0].locations] == [(5, 19)]
+    assert errors[1].locations == []
+
+
+def test_rules_see_valid_documents_only():
+    recorder = Recorder()
+
+    errors = validation_errors([recorder], "{ user { missing } }")
+
+    assert errors
+    assert recorder.events == []
+
+
+def test_rule_exceptions_propagate():
+    class Failing:
+        def enter_field(self, node, context):
+            raise ValueError(f"cannot check {node.name}")
+
+    with pytest.raises(ValueError, match="cannot check user"):
+        validation_errors([Failing()])
@@ -0,0 +1,35 @@
+import pytest
+
+from rustberry import QueryCompiler, SchemaError
+
+INVALID_SCHEMA = """
+type Query {
+  user: Missing
+}
+"""
+
+
+def test_syntax_error():
+    with pytest.raises(SchemaError, match="Schema could not be parsed") as exc_info:
+        QueryCompiler("type Query {")
+
+    assert exc_info.value.errors
+    assert exc_info.value.errors[0].locations[0].line == 1
+
+
+def test_invalid_schema():
+    with pytest.raises(SchemaError, match="Schema is invalid") as exc_info:
+        QueryCompiler(INVALID_SCHEMA)
+
+    [error] = exc_info.value.errors
+    assert "Missing" in error.message
+    assert (error.locations[0].line, error.locations[0].column) == (3, 9)
+
+
+def test_assume_valid_skips_validation():
+    QueryCompiler(INVALID_SCHEMA, assume_valid=True)
+
+
+def test_assume_valid_still_reports_syntax_errors():
+    with pytest.raises(SchemaError, match="Schema could not be parsed"):
+        QueryCompiler("type Query {", assume_valid=True)
@@ -0,0 +1,68 @@
+from collections import OrderedDict
+from types import MappingProxyType
+
+import pytest
+
+from rustberry import QueryCompiler, SchemaError
+
+QUERY = "type Query { user: User }"
+USER = "type User { name: String }"
+EXTENSION = "extend type User { age: Int }"
+
+
+def test_sdl_text():
+    compiler = QueryCompiler(f"{QUERY}\n{USER}")
+
+    assert compiler.validate(compiler.parse("{ user { name } }"))
+
+
+@pytest.mark.parametrize(
+    "mapping_type", [dict, OrderedDict, lambda files: MappingProxyType(dict(files))]
+)
+def test_mapping_of_file_names(mapping_type):
+    compiler = QueryCompiler(
+        mapping_type({"query.graphql": QUERY, "user.graphql": USER, "ext.graphql": EXTENSION})
+    )
+
+    assert compiler.validate(compiler.parse("{ user { name age } }"))
+
+
+def test_invalid_file_raises_schema_error():
+    with pytest.raises(SchemaError) as exc_info:
+        QueryCompiler({"query.graphql": QUERY, "user.graphql": "type User { name: Unknown }"})
+
+    assert exc_info.value.errors
+
+
+@pytest.mark.parametrize("sequence_type", [list, tuple])
+def test_paths(tmp_path, sequence_type):
+    (tmp_path / "query.graphql").write_text(QUERY)
+    (tmp_path / "user.graphql").write_text(USER)
+
+    compiler = QueryCompiler(
+        sequence_type([tmp_path / "query.graphql", str(tmp_path / "user.graphql")])
+    )
+
+    assert compiler.validate(compiler.parse("{ user { name } }"))
+
+
+def test_missing_path(tmp_path):
+    with pytest.raises(OSError, match="Could not read schema file"):
+        QueryCompiler([tmp_path / "missing.graphql"])
+
+
+@pytest.mark.parametrize("schema", [b"type Query { name: String }", 42, None, {QUERY}])
+def test_rejects_other_types(schema):
+    with pytest.raises(TypeError, match="schema must be SDL text"):
+        QueryCompiler(schema)
+
+
+def test_rejects_non_path_items():
+    with pytest.raises(TypeError, match="schema paths must be str or os.PathLike"):
+        QueryCompiler([b"schema.graphql"])
+
+
+def test_rejects_non_str_mapping_values():
+    with pytest.raises(TypeError, match="schema mappings must map file names to SDL text"):
+        QueryCompiler({"schema.graphql": b"type Query { name: String }"})
+
@@ -0,0 +1,73 @@
+from concurrent.futures import ThreadPoolExecutor
+
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  name: String
+  age: Int
+}
+"""
+
+
+def messages(errors):
+    return [error.message for error in errors]
+
+
+def test_results_in_source_order():
+    compiler = QueryCompiler(SCHEMA)
+
+    results = compiler.validate_many(["{ name }", "{ unknown }", "{ age }"])
+
+    assert len(results) == 3
+    assert results[0] == []
+    assert len(results[1]) == 1
+    assert results[2] == []
+
+
+def test_matches_validation_errors():
+    compiler = QueryCompiler(SCHEMA)
+    source = "{ unknown name { age } }"
+
+    [errors] = compiler.validate_many([source])
+
+    assert messages(errors) == messages(compiler.validation_errors(compiler.parse(source)))
+
+
+def test_reports_parse_errors():
+    compiler = QueryCompiler(SCHEMA)
+
+    [errors] = compiler.validate_many(["{ name"])
+
+    assert errors
+
+
+def test_applies_compiler_options():
+    compiler = QueryCompiler(SCHEMA, max_cost=-1)
+
+    [errors] = compiler.validate_many(["{ name }"])
+
+    assert [error.extensions["code"] for error in errors] == ["MaxCostExceeded"]
+
+
+def test_large_batch_uses_cache():
+    compiler = QueryCompiler(SCHEMA, cache_size=10)
+    sources = ["{ name }", "{ age }"] * 100
+
+    results = compiler.validate_many(sources)
+
+    assert results == [[]] * 200
+    assert compiler.cache_info().currsize == 2
+
+
+def test_compiler_shared_between_threads():
+    compiler = QueryCompiler(SCHEMA, cache_size=10)
+
+    def validate(index):
+        source = "{ name }" if index % 2 else "{ unknown }"
+        return compiler.validate(compiler.parse(source))
+
+    with ThreadPoolExecutor(max_workers=8) as executor:
+        results = list(executor.map(validate, range(100)))
+
+    assert results == [bool(index % 2) for index in range(100)]
@@ -0,0 +1,48 @@
+from rustberry import QueryCompiler
+
+SCHEMA = """
+type Query {
+  user(id: ID!): User
+}
+
+type User {
+  name: String
+}
+"""
+
+
+def validation_errors(query):
+    compiler = QueryCompiler(SCHEMA)
+    return compiler.validation_errors(compiler.parse(query))
+
+
+def test_valid_document():
+    assert validation_errors('{ user(id: 1) { name } }') == []
+
+
+def test_undefined_field():
+    [error] = validation_errors("{\n  user(id: 1) {\n    missing\n  }\n}")
+
+    assert error.message == 'Cannot query field "missing" on type "User".'
+    assert error.extensions == {"code": "UndefinedField"}
+    assert [(location.line, location.column) for location in error.locations] == [(3, 5)]
+    assert error.formatted == {
+        "message": 'Cannot query field "missing" on type "User".',
+        "locations": [{"line": 3, "column": 5}],
+        "extensions": {"code": "UndefinedField"},
+    }
+
+
+def test_every_error_is_reported():
+    errors = validation_errors("{ user { name } other }")
+
+    assert len(errors) == 2
+    assert all(error.extensions["code"] for error in errors)
+    assert sorted(error.locations[0].column for error in errors) == [3, 17]
+
+
+def test_validate_matches_validation_errors():
+    compiler = QueryCompiler(SCHEMA)
+
+    assert compiler.validate(compiler.parse("{ user(id: 1) { name } }"))
+    assert not compiler.validate(compiler.parse("{ user { name } }"))